@@ -0,0 +1,54 @@
+//  ENABLED.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:45:57
+//  Last edited:
+//    16 Oct 2026, 15:45:57
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Benchmarks the cost of asking the logger whether a level is enabled,
+//!   which is what libraries pay for every disabled `log_enabled!()` or
+//!   `trace!()`.
+// 
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter};
+use log::{Level, Log as _, Metadata};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Creates a logger with three writers that write everything, in the given mode.
+fn logger(mode: DebugMode) -> HumanLogger {
+    let all: Vec<Level> = vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ];
+    HumanLogger::new((0..3).map(|i| LogWriter::new(std::io::sink(), ColourChoice::No, all.clone(), format!("sink{i}"))), mode)
+}
+
+
+
+
+
+/***** BENCHMARKS *****/
+/// Benchmarks `Log::enabled()` for a level that no writer writes, and one that every writer writes.
+fn enabled(c: &mut Criterion) {
+    let trace: Metadata = Metadata::builder().level(Level::Trace).target("myapp::server").build();
+    let warn: Metadata = Metadata::builder().level(Level::Warn).target("myapp::server").build();
+
+    // No directives, so the mode decides
+    let plain: HumanLogger = logger(DebugMode::HumanFriendly);
+    c.bench_function("enabled/disabled_level", |b| b.iter(|| plain.enabled(black_box(&trace))));
+    c.bench_function("enabled/enabled_level", |b| b.iter(|| plain.enabled(black_box(&warn))));
+
+    // With directives that only enable some targets, so the target has to be checked too
+    let targeted: HumanLogger = logger(DebugMode::HumanFriendly).with_directives("myapp::db=trace,hyper=warn".parse().unwrap());
+    c.bench_function("enabled/targeted_level", |b| b.iter(|| targeted.enabled(black_box(&trace))));
+}
+
+
+
+criterion_group!(benches, enabled);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+//  FORMAT.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:50:42
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Benchmarks the cost of logging a record that is written, i.e., of
+//!   formatting it and handing it to every writer.
+// 
+
+use std::hint::black_box;
+use std::io::Write;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use humanlog::{ColourChoice, DebugMode, FormatContext, Formatter, FullFormatter, HumanLogger, JsonFormatter, LogWriter};
+use log::{Level, Log as _, Record};
+
+
+/***** HELPERS *****/
+/// A [`FullFormatter`] that names no layout, which makes every writer render the record itself like before records were shared.
+struct PerWriter;
+impl Formatter for PerWriter {
+    #[inline]
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> { FullFormatter.format(writer, record, ctx) }
+}
+
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Creates a writer that writes everything to nowhere.
+fn sink(colour: ColourChoice, label: &str) -> LogWriter {
+    LogWriter::new(std::io::sink(), colour, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], label)
+}
+
+/// Logs a typical record with the given logger.
+fn log(logger: &HumanLogger, peer: &str, ms: u64) {
+    logger.log(&Record::builder().level(Level::Info).target("myapp::server").module_path(Some("myapp::server")).file(Some("src/server.rs")).line(Some(128)).args(format_args!("Accepted connection from {peer} after {ms} ms")).build());
+}
+
+
+
+
+
+/***** BENCHMARKS *****/
+/// Benchmarks `Log::log()` for one writer, for three writers with the same layout (both shared and rendered per writer), and for three writers with different layouts.
+fn format(c: &mut Criterion) {
+    let single: HumanLogger = HumanLogger::new([ sink(ColourChoice::No, "sink0") ], DebugMode::Full);
+    c.bench_function("format/single_writer", |b| b.iter(|| log(&single, black_box("10.0.0.17"), black_box(42))));
+
+    let same: HumanLogger = HumanLogger::new((0..3).map(|i| sink(ColourChoice::No, &format!("sink{i}"))), DebugMode::Full);
+    c.bench_function("format/same_layout", |b| b.iter(|| log(&same, black_box("10.0.0.17"), black_box(42))));
+
+    // The baseline to compare the above with, which renders the same records once for every writer
+    let per_writer: HumanLogger = HumanLogger::new((0..3).map(|i| sink(ColourChoice::No, &format!("sink{i}")).with_formatter(PerWriter)), DebugMode::Full);
+    c.bench_function("format/same_layout_per_writer", |b| b.iter(|| log(&per_writer, black_box("10.0.0.17"), black_box(42))));
+
+    let mixed: HumanLogger = HumanLogger::new([ sink(ColourChoice::No, "plain"), sink(ColourChoice::Yes, "coloured"), sink(ColourChoice::No, "json").with_formatter(JsonFormatter) ], DebugMode::Full);
+    c.bench_function("format/mixed_layouts", |b| b.iter(|| log(&mixed, black_box("10.0.0.17"), black_box(42))));
+}
+
+
+
+criterion_group!(benches, format);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//  KEY VALUES.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:00:15
+//  Last edited:
+//    16 Oct 2026, 15:00:15
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Shows an example where structured key/value pairs are attached to
+//!   log messages.
+// 
+
+use humanlog::{DebugMode, HumanLogger};
+use log::{error, info};
+
+
+/**** ENTRYPOINT *****/
+fn main() {
+    // Setup the logger to write to the terminal with a mode that can be chosen by the first argument
+    let mode: DebugMode = DebugMode::from_num(std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(1));
+    if let Err(err) = HumanLogger::terminal(mode).init() {
+        eprintln!("WARNING: Failed to initialize logger: {} (no logging enabled for this session)", err);
+    }
+
+    // Write some messages with key/value pairs attached!
+    info!(user_id = 42, path = "/x"; "Request done");
+    error!(user_id = 42, path = "/x\n\"quoted\"", retry = false; "Request failed");
+}
@@ -0,0 +1,570 @@
+//  BACKGROUND.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:11:49
+//  Last edited:
+//    16 Oct 2026, 15:17:29
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the background thread that writes records to the
+//!   [`LogWriter`](crate::LogWriter)s if the
+//!   [`HumanLogger`](crate::HumanLogger) runs in asynchronous mode.
+// 
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use console::style;
+use parking_lot::{Condvar, Mutex, MutexGuard};
+
+
+/***** TYPES *****/
+/// A writer that can be moved to the writer thread.
+pub(crate) type BoxedWriter = Box<dyn Send + Sync + Write>;
+
+
+
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// Whether the current thread is a writer thread. Used to prevent it from blocking on its own queue if a writer happens to log something.
+    static IS_WRITER_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The queue to drain when the process exits, if any.
+static EXIT_QUEUE: OnceLock<Arc<Queue>> = OnceLock::new();
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Drains the [`EXIT_QUEUE`] before the process exits.
+extern "C" fn drain_at_exit() {
+    if let Some(queue) = EXIT_QUEUE.get() {
+        queue.flush();
+    }
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines what happens if a record is logged while the queue of the writer thread is full.
+/// 
+/// # Examples
+/// With `OverflowPolicy::DropNewest`, records that don't fit in the queue anymore are lost:
+/// ```rust
+/// use std::io::{self, Write};
+/// use std::sync::mpsc::{self, Receiver, Sender};
+/// use std::sync::{Arc, Mutex, MutexGuard};
+/// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter, OverflowPolicy};
+/// use log::{warn, Level};
+/// 
+/// /// A writer that says when it starts writing a record, and then waits until the gate is open.
+/// struct Gate { memory: Arc<Mutex<Vec<u8>>>, gate: Arc<Mutex<()>>, writing: Sender<()> }
+/// impl Write for Gate {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         let _ = self.writing.send(());
+///         let _open = self.gate.lock().unwrap();
+///         self.memory.lock().unwrap().write(buf)
+///     }
+///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// }
+/// 
+/// let (memory, gate): (Arc<Mutex<Vec<u8>>>, Arc<Mutex<()>>) = (Arc::default(), Arc::default());
+/// let (writing, started): (Sender<()>, Receiver<()>) = mpsc::channel();
+/// let writer: LogWriter = LogWriter::new(Gate { memory: memory.clone(), gate: gate.clone(), writing }, ColourChoice::No, [ Level::Warn ], "gate");
+/// HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly).with_async(2, OverflowPolicy::DropNewest).init().unwrap();
+/// 
+/// // Keep the writer thread busy with the first record while the queue (of two records) fills up
+/// let closed: MutexGuard<()> = gate.lock().unwrap();
+/// warn!("Record 1");
+/// started.recv().unwrap();
+/// warn!("Record 2");
+/// warn!("Record 3");
+/// warn!("Record 4");
+/// drop(closed);
+/// 
+/// // The fourth record was dropped, which is reported before the next record once there is space again
+/// log::logger().flush();
+/// warn!("Record 5");
+/// log::logger().flush();
+/// assert_eq!(String::from_utf8_lossy(&memory.lock().unwrap()), "WARNING: Record 1\nWARNING: Record 2\nWARNING: Record 3\nWARNING: Dropped 1 log message(s) because the writer thread could not keep up\nWARNING: Record 5\n");
+/// ```
+/// 
+/// With `OverflowPolicy::DropOldest`, they push out the oldest record that is still waiting instead:
+/// ```rust
+/// use std::io::{self, Write};
+/// use std::sync::mpsc::{self, Receiver, Sender};
+/// use std::sync::{Arc, Mutex, MutexGuard};
+/// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter, OverflowPolicy};
+/// use log::{warn, Level};
+/// 
+/// /// A writer that says when it starts writing a record, and then waits until the gate is open.
+/// struct Gate { memory: Arc<Mutex<Vec<u8>>>, gate: Arc<Mutex<()>>, writing: Sender<()> }
+/// impl Write for Gate {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         let _ = self.writing.send(());
+///         let _open = self.gate.lock().unwrap();
+///         self.memory.lock().unwrap().write(buf)
+///     }
+///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// }
+/// 
+/// let (memory, gate): (Arc<Mutex<Vec<u8>>>, Arc<Mutex<()>>) = (Arc::default(), Arc::default());
+/// let (writing, started): (Sender<()>, Receiver<()>) = mpsc::channel();
+/// let writer: LogWriter = LogWriter::new(Gate { memory: memory.clone(), gate: gate.clone(), writing }, ColourChoice::No, [ Level::Warn ], "gate");
+/// HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly).with_async(2, OverflowPolicy::DropOldest).init().unwrap();
+/// 
+/// // Keep the writer thread busy with the first record while the queue (of two records) fills up
+/// let closed: MutexGuard<()> = gate.lock().unwrap();
+/// warn!("Record 1");
+/// started.recv().unwrap();
+/// warn!("Record 2");
+/// warn!("Record 3");
+/// warn!("Record 4");
+/// drop(closed);
+/// 
+/// // The second record made way for the fourth, which is reported before the next record once there is space again
+/// log::logger().flush();
+/// warn!("Record 5");
+/// log::logger().flush();
+/// assert_eq!(String::from_utf8_lossy(&memory.lock().unwrap()), "WARNING: Record 1\nWARNING: Record 3\nWARNING: Record 4\nWARNING: Dropped 1 log message(s) because the writer thread could not keep up\nWARNING: Record 5\n");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum OverflowPolicy {
+    /// The logging thread waits until there is space in the queue again. No records are lost, but logging may stall if the writers can't keep up.
+    #[default]
+    Block,
+    /// The new record is dropped.
+    DropNewest,
+    /// The oldest record in the queue is dropped to make space for the new one.
+    DropOldest,
+}
+
+
+
+/// A message sent to the writer thread.
+enum Message {
+    /// A rendered record that needs to be written to the writer with the given index.
+    Record { writer: usize, bytes: Vec<u8> },
+    /// A request to add a new writer, which gets the next index.
+    Add(BoxedWriter),
+    /// A request to replace the writer with the given index.
+    Replace { writer: usize, new: BoxedWriter },
+    /// A request to flush all writers.
+    Flush,
+    /// A request to flush all writers and then quit.
+    Shutdown,
+}
+
+/// The part of the [`Queue`] that is protected by its lock.
+struct State {
+    /// The messages that still have to be processed.
+    messages : VecDeque<Message>,
+    /// The number of [`Message::Record`]s in `messages`.
+    records  : usize,
+    /// The number of records that were dropped since the last time [`Queue::take_dropped()`] was called.
+    dropped  : u64,
+    /// The first error that occurred per writer since it was last reported to the logging thread, which happens the next time it writes to it.
+    errors   : Vec<Option<io::Error>>,
+    /// The number of flushes that were requested.
+    flushes  : u64,
+    /// The number of flushes that were completed.
+    flushed  : u64,
+    /// Whether the writer thread is still alive.
+    alive    : bool,
+}
+
+/// The bounded queue through which records are handed to the writer thread.
+pub(crate) struct Queue {
+    /// The state of the queue.
+    state     : Mutex<State>,
+    /// The maximum number of records in the queue.
+    capacity  : usize,
+    /// What to do if the queue is full.
+    overflow  : OverflowPolicy,
+    /// Signals the writer thread that there are new messages.
+    not_empty : Condvar,
+    /// Signals logging threads that there is space in the queue.
+    not_full  : Condvar,
+    /// Signals logging threads that a flush has been completed.
+    flushed   : Condvar,
+}
+impl Queue {
+    /// Pushes a new record to the queue, applying the overflow policy if it's full.
+    /// 
+    /// # Arguments
+    /// - `writer`: The index of the writer to write the record to.
+    /// - `bytes`: The rendered record.
+    fn push(&self, writer: usize, bytes: Vec<u8>) {
+        let mut state: MutexGuard<State> = self.state.lock();
+        while state.records >= self.capacity {
+            // The writer thread cannot wait for itself, so it always drops
+            let policy: OverflowPolicy = if IS_WRITER_THREAD.with(Cell::get) { OverflowPolicy::DropNewest } else { self.overflow };
+            match policy {
+                OverflowPolicy::Block => {
+                    if !state.alive { break; }
+                    self.not_full.wait(&mut state);
+                },
+                OverflowPolicy::DropNewest => {
+                    state.dropped += 1;
+                    return;
+                },
+                OverflowPolicy::DropOldest => {
+                    let oldest: Option<usize> = state.messages.iter().position(|msg| matches!(msg, Message::Record { .. }));
+                    if let Some(oldest) = oldest {
+                        state.messages.remove(oldest);
+                        state.records -= 1;
+                    }
+                    state.dropped += 1;
+                },
+            }
+        }
+
+        // If the thread is gone, there's no point in queueing anything anymore
+        if !state.alive { return; }
+        state.messages.push_back(Message::Record { writer, bytes });
+        state.records += 1;
+        self.not_empty.notify_one();
+    }
+
+    /// Takes an error that the writer thread encountered for the given writer, if any.
+    /// 
+    /// # Arguments
+    /// - `writer`: The index of the writer to take the error of.
+    /// 
+    /// # Returns
+    /// The [`io::Error`] that occurred while writing to or flushing the writer on the writer thread.
+    fn take_error(&self, writer: usize) -> Option<io::Error> { self.state.lock().errors[writer].take() }
+
+    /// Returns the number of records dropped since the last call, but only if there is space in the queue to report it.
+    /// 
+    /// # Returns
+    /// The number of dropped records, or `0` if none were dropped or the queue is still full.
+    pub(crate) fn take_dropped(&self) -> u64 {
+        let mut state: MutexGuard<State> = self.state.lock();
+        if state.records < self.capacity { std::mem::take(&mut state.dropped) } else { 0 }
+    }
+
+    /// Replaces the writer with the given index once all records queued before it have been written.
+    /// 
+    /// # Arguments
+    /// - `writer`: The index of the writer to replace.
+    /// - `new`: The writer to replace it with.
+    fn replace(&self, writer: usize, new: BoxedWriter) {
+        let mut state: MutexGuard<State> = self.state.lock();
+        state.messages.push_back(Message::Replace { writer, new });
+        self.not_empty.notify_one();
+    }
+
+    /// Waits until the writer thread has written and flushed everything that is in the queue now.
+    pub(crate) fn flush(&self) {
+        // The writer thread cannot wait for itself
+        if IS_WRITER_THREAD.with(Cell::get) { return; }
+
+        let mut state: MutexGuard<State> = self.state.lock();
+        state.flushes += 1;
+        let ticket: u64 = state.flushes;
+        state.messages.push_back(Message::Flush);
+        self.not_empty.notify_one();
+        while state.alive && state.flushed < ticket {
+            self.flushed.wait(&mut state);
+        }
+    }
+}
+
+
+
+/// A writer that hands everything written to it to the writer thread.
+/// 
+/// Every call to `write()` is treated as one record, which is why the HumanLogger renders records completely before writing them.
+pub(crate) struct QueuedWriter {
+    /// The index of the actual writer on the writer thread.
+    writer : usize,
+    /// The queue to push records to.
+    queue  : Arc<Queue>,
+}
+impl Write for QueuedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(err) = self.queue.take_error(self.writer) { return Err(err); }
+        self.queue.push(self.writer, buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.queue.flush();
+        match self.queue.take_error(self.writer) {
+            Some(err) => Err(err),
+            None      => Ok(()),
+        }
+    }
+}
+
+
+
+/// The writer that a [`LogWriter`](crate::LogWriter) actually writes to, which is either the user's writer or a [`QueuedWriter`] if we log asynchronously.
+pub(crate) enum Sink {
+    /// The records are written directly.
+    Direct(BoxedWriter),
+    /// The records are handed to the writer thread.
+    Queued(QueuedWriter),
+}
+impl Sink {
+    /// Replaces the writer that records are (eventually) written to.
+    /// 
+    /// # Arguments
+    /// - `new`: The writer to replace the current one with.
+    pub(crate) fn replace(&mut self, new: BoxedWriter) {
+        match self {
+            Self::Direct(writer) => { *writer = new; },
+            Self::Queued(queued) => queued.queue.replace(queued.writer, new),
+        }
+    }
+}
+impl Write for Sink {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Direct(writer) => writer.write(buf),
+            Self::Queued(queued) => queued.write(buf),
+        }
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Direct(writer) => writer.write_all(buf),
+            Self::Queued(queued) => queued.write_all(buf),
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Direct(writer) => writer.flush(),
+            Self::Queued(queued) => queued.flush(),
+        }
+    }
+}
+
+
+
+/// Marks the writer thread as dead when it stops, even if it panics.
+struct AliveGuard {
+    /// The queue of the writer thread.
+    queue : Arc<Queue>,
+}
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        let mut state: MutexGuard<State> = self.queue.state.lock();
+        state.alive = false;
+        self.queue.not_full.notify_all();
+        self.queue.flushed.notify_all();
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Owns the writer thread of a [`HumanLogger`](crate::HumanLogger) running in asynchronous mode.
+/// 
+/// Dropping it flushes the queue and stops the thread.
+pub(crate) struct Background {
+    /// The queue of the writer thread.
+    queue   : Arc<Queue>,
+    /// The channel over which the writers are sent to the writer thread, until they are.
+    writers : Mutex<Option<Sender<Vec<BoxedWriter>>>>,
+    /// The handle of the writer thread.
+    handle  : Option<JoinHandle<()>>,
+}
+impl Background {
+    /// Starts a new writer thread.
+    /// 
+    /// The thread does not write anything until it is given its writers with [`Background::attach()`].
+    /// 
+    /// # Arguments
+    /// - `capacity`: The maximum number of records that may be queued at a time.
+    /// - `overflow`: What to do if the queue is full.
+    /// 
+    /// # Returns
+    /// A new Background that owns the thread.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to spawn the thread.
+    pub(crate) fn start(capacity: usize, overflow: OverflowPolicy) -> io::Result<Self> {
+        let queue: Arc<Queue> = Arc::new(Queue {
+            state : Mutex::new(State {
+                messages : VecDeque::new(),
+                records  : 0,
+                dropped  : 0,
+                errors   : vec![],
+                flushes  : 0,
+                flushed  : 0,
+                alive    : true,
+            }),
+            capacity  : capacity.max(1),
+            overflow,
+            not_empty : Condvar::new(),
+            not_full  : Condvar::new(),
+            flushed   : Condvar::new(),
+        });
+
+        // Spawn the thread, which first waits for its writers
+        let (sender, receiver) = mpsc::channel();
+        let guard: AliveGuard = AliveGuard { queue: queue.clone() };
+        let handle: JoinHandle<()> = thread::Builder::new().name("humanlog".into()).spawn(move || {
+            if let Ok(writers) = receiver.recv() { run(guard, writers); }
+        })?;
+        Ok(Self { queue, writers: Mutex::new(Some(sender)), handle: Some(handle) })
+    }
+
+    /// Hands the given writers to the writer thread.
+    /// 
+    /// # Arguments
+    /// - `writers`: The actual writers. Their indices are used to refer to them in the returned [`QueuedWriter`]s.
+    /// 
+    /// # Returns
+    /// A [`QueuedWriter`] for every given writer, in the same order.
+    /// 
+    /// # Panics
+    /// This function panics if the writers were already attached.
+    pub(crate) fn attach(&self, writers: Vec<BoxedWriter>) -> Vec<QueuedWriter> {
+        let n: usize = writers.len();
+        self.queue.state.lock().errors = (0..n).map(|_| None).collect();
+        // If the thread is already gone, the QueuedWriters will simply drop everything
+        let _ = self.writers.lock().take().expect("Writers were already attached").send(writers);
+        (0..n).map(|writer| QueuedWriter { writer, queue: self.queue.clone() }).collect()
+    }
+
+    /// Hands another writer to the writer thread, after the initial ones have been attached.
+    /// 
+    /// # Arguments
+    /// - `writer`: The actual writer.
+    /// 
+    /// # Returns
+    /// A [`QueuedWriter`] that writes to the given writer.
+    pub(crate) fn add(&self, writer: BoxedWriter) -> QueuedWriter {
+        let mut state: MutexGuard<State> = self.queue.state.lock();
+        let index: usize = state.errors.len();
+        state.errors.push(None);
+        state.messages.push_back(Message::Add(writer));
+        self.queue.not_empty.notify_one();
+        QueuedWriter { writer: index, queue: self.queue.clone() }
+    }
+
+    /// Makes sure the queue is drained when the process exits.
+    /// 
+    /// Only the first Background ever registered is drained, which is fine since there can only ever be one logger.
+    pub(crate) fn drain_at_exit(&self) {
+        if EXIT_QUEUE.set(self.queue.clone()).is_ok() {
+            // SAFETY: `drain_at_exit` is a plain function without arguments that does not unwind.
+            if unsafe { libc::atexit(drain_at_exit) } != 0 {
+                eprintln!("{}: Failed to register exit handler (logs written right before the process exits may be lost)", style("WARNING").yellow().bold());
+            }
+        }
+    }
+
+    /// Returns the queue of the writer thread.
+    #[inline]
+    pub(crate) fn queue(&self) -> &Queue { &self.queue }
+}
+impl Drop for Background {
+    fn drop(&mut self) {
+        // Dropping the sender stops the thread if it never received its writers
+        *self.writers.get_mut() = None;
+        {
+            let mut state: MutexGuard<State> = self.queue.state.lock();
+            state.messages.push_back(Message::Shutdown);
+            self.queue.not_empty.notify_one();
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+
+
+/// The main function of the writer thread.
+/// 
+/// # Arguments
+/// - `guard`: The [`AliveGuard`] that gives access to the queue, and marks the thread as dead when it quits.
+/// - `writers`: The writers to write to.
+fn run(guard: AliveGuard, mut writers: Vec<BoxedWriter>) {
+    IS_WRITER_THREAD.with(|flag| flag.set(true));
+    let queue: &Queue = &guard.queue;
+
+    // Records the first error of a writer until the logging thread picks it up, which decides what to do with the writer
+    let report = |writer: usize, err: io::Error| {
+        let mut state: MutexGuard<State> = queue.state.lock();
+        if state.errors[writer].is_none() { state.errors[writer] = Some(err); }
+    };
+    let flush_all = |writers: &mut Vec<BoxedWriter>| {
+        for (i, writer) in writers.iter_mut().enumerate() {
+            if let Err(err) = writer.flush() { report(i, err); }
+        }
+    };
+
+    let mut dirty: bool = false;
+    loop {
+        // Wait for the next message, flushing the writers whenever we run out of work
+        let msg: Message = {
+            let mut state: MutexGuard<State> = queue.state.lock();
+            if state.messages.is_empty() && dirty {
+                MutexGuard::unlocked(&mut state, || flush_all(&mut writers));
+                dirty = false;
+                continue;
+            }
+            while state.messages.is_empty() {
+                queue.not_empty.wait(&mut state);
+            }
+            let msg: Message = state.messages.pop_front().unwrap();
+            if let Message::Record { .. } = msg {
+                state.records -= 1;
+                queue.not_full.notify_one();
+            }
+            msg
+        };
+
+        // Process it
+        match msg {
+            Message::Record { writer, bytes } => {
+                if let Err(err) = writers[writer].write_all(&bytes) { report(writer, err); }
+                dirty = true;
+            },
+
+            Message::Add(new) => writers.push(new),
+
+            Message::Replace { writer, new } => {
+                writers[writer] = new;
+                queue.state.lock().errors[writer] = None;
+            },
+
+            Message::Flush => {
+                flush_all(&mut writers);
+                dirty = false;
+                let mut state: MutexGuard<State> = queue.state.lock();
+                state.flushed += 1;
+                queue.flushed.notify_all();
+            },
+
+            Message::Shutdown => {
+                flush_all(&mut writers);
+                return;
+            },
+        }
+    }
+}
@@ -0,0 +1,493 @@
+//  BUILDER.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:54:58
+//  Last edited:
+//    16 Oct 2026, 15:57:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the [`HumanLoggerBuilder`] and [`LogWriterBuilder`], which
+//!   construct a [`HumanLogger`] and [`LogWriter`]s option by option.
+// 
+
+use std::io::Write;
+use std::sync::Arc;
+
+use log::{Metadata, SetLoggerError};
+use parking_lot::{Mutex, RwLock};
+
+use crate::gate::Gate;
+use crate::recovery::WriterState;
+use crate::{is_terminal, timestamp, Alignment, ColourChoice, Config, DebugMode, Directives, ErrorPolicy, Formatter, HumanLogger, LevelSet, LogWriter, LoggerHandle, MultilineStyle, OverflowPolicy, PrefixFields, PrettyFormatter, SharedWriter, Theme, TimestampStyle};
+
+
+/***** LIBRARY *****/
+impl HumanLogger {
+    /// Returns a builder for a HumanLogger, which can be used to set its options one by one.
+    /// 
+    /// # Returns
+    /// A new [`HumanLoggerBuilder`] that logs in `DebugMode::HumanFriendly` to stdout and stderr, until told otherwise.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, LogWriter, TimestampStyle};
+    /// use log::Level;
+    /// 
+    /// let file_logger: LogWriter = LogWriter::builder(std::io::sink()).with_levels([ Level::Error, Level::Warn, Level::Info ]).with_label("file").build();
+    /// if let Err(err) = HumanLogger::builder().with_mode(DebugMode::Debug).with_writer(LogWriter::stderr()).with_writer(file_logger).with_timestamp(TimestampStyle::utc()).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn builder() -> HumanLoggerBuilder { HumanLoggerBuilder::new() }
+}
+
+impl LogWriter {
+    /// Returns a builder for a LogWriter that writes to the given `Write`r, which can be used to set its options one by one.
+    /// 
+    /// # Arguments
+    /// - `writer`: The handle or other object that implement `Write` and we will write to.
+    /// 
+    /// # Returns
+    /// A new [`LogWriterBuilder`] for a writer that writes every level, with automatic colour selection, labelled `writer`, until told otherwise.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, JsonFormatter, LogWriter};
+    /// use log::Level;
+    /// 
+    /// let logger: LogWriter = LogWriter::builder(std::io::stdout()).with_colour(ColourChoice::No).with_levels([ Level::Error, Level::Warn ]).with_label("stdout").with_formatter(JsonFormatter).build();
+    /// if let Err(err) = HumanLogger::new(vec![ logger ], DebugMode::Debug).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    pub fn builder(writer: impl 'static + Send + Sync + Write) -> LogWriterBuilder {
+        // Find out what we're writing to before we lose its type
+        let is_terminal: bool = is_terminal(&writer);
+        LogWriterBuilder {
+            writer : LogWriter {
+                label       : "writer".into(),
+                writer      : Box::new(writer),
+                is_terminal,
+                // Resolved once we know the colour choice
+                colour      : false,
+                formatter   : Box::new(PrettyFormatter),
+                directives  : Directives::new(),
+                policy      : ErrorPolicy::default(),
+                timestamp   : None,
+                fields      : None,
+                filter      : LevelSet::all(),
+                predicate   : None,
+            },
+            colour : ColourChoice::Auto,
+        }
+    }
+}
+
+
+
+/// Builds a [`HumanLogger`] option by option.
+/// 
+/// Create one with [`HumanLogger::builder()`]. Every option that isn't set keeps the same default as with [`HumanLogger::new()`].
+pub struct HumanLoggerBuilder {
+    /// The writers to write to, or [`None`] to write to stdout and stderr.
+    writers      : Option<Vec<LogWriter>>,
+    /// The mode to log in.
+    mode         : DebugMode,
+    /// The directives that decide which levels are logged per target.
+    directives   : Directives,
+    /// The style in which to write timestamps.
+    timestamp    : TimestampStyle,
+    /// How to lay out the continuation lines of multi-line messages.
+    multiline    : MultilineStyle,
+    /// The labels and colours with which to write records.
+    theme        : Theme,
+    /// The widths to which to align the columns of the prefix.
+    alignment    : Alignment,
+    /// Which thread and process fields to write.
+    fields       : PrefixFields,
+    /// The capacity and overflow policy of the writer thread, if we log asynchronously.
+    asynchronous : Option<(usize, OverflowPolicy)>,
+}
+impl Default for HumanLoggerBuilder {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl HumanLoggerBuilder {
+    /// Constructor for the HumanLoggerBuilder.
+    /// 
+    /// # Returns
+    /// A new HumanLoggerBuilder that logs in `DebugMode::HumanFriendly` to stdout and stderr, until told otherwise.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            writers      : None,
+            mode         : DebugMode::HumanFriendly,
+            directives   : Directives::new(),
+            timestamp    : TimestampStyle::default(),
+            multiline    : MultilineStyle::default(),
+            theme        : Theme::default(),
+            alignment    : Alignment::default(),
+            fields       : PrefixFields::default(),
+            asynchronous : None,
+        }
+    }
+
+
+
+    /// Changes the mode to log in.
+    /// 
+    /// # Arguments
+    /// - `mode`: The `DebugMode` that decides both which `Level`s are logged, and how the messages are formatted.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new mode set.
+    #[inline]
+    pub fn with_mode(mut self, mode: DebugMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a writer to write to.
+    /// 
+    /// Once any writer is added, the logger no longer writes to stdout and stderr by default. Add `LogWriter::stdout()` and `LogWriter::stderr()` to keep doing so.
+    /// 
+    /// # Arguments
+    /// - `writer`: The `LogWriter` to add.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the writer added.
+    #[inline]
+    pub fn with_writer(mut self, writer: LogWriter) -> Self {
+        self.writers.get_or_insert_with(Vec::new).push(writer);
+        self
+    }
+
+    /// Adds a number of writers to write to.
+    /// 
+    /// Once this is called, the logger no longer writes to stdout and stderr by default, even if no writers were given.
+    /// 
+    /// # Arguments
+    /// - `writers`: The `LogWriter`s to add.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the writers added.
+    #[inline]
+    pub fn with_writers(mut self, writers: impl IntoIterator<Item = LogWriter>) -> Self {
+        self.writers.get_or_insert_with(Vec::new).extend(writers);
+        self
+    }
+
+    /// Sets `RUST_LOG`-style directives that decide which levels are logged per target.
+    /// 
+    /// See `HumanLogger::with_directives()` for more information.
+    /// 
+    /// # Arguments
+    /// - `directives`: The `Directives` to apply.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new directives set.
+    #[inline]
+    pub fn with_directives(mut self, directives: Directives) -> Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Changes the style in which timestamps are written.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The `TimestampStyle` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new timestamp style set.
+    #[inline]
+    pub fn with_timestamp(mut self, timestamp: TimestampStyle) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Changes how the continuation lines of multi-line messages are laid out.
+    /// 
+    /// # Arguments
+    /// - `multiline`: The `MultilineStyle` to lay out continuation lines in.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new multiline style set.
+    #[inline]
+    pub fn with_multiline(mut self, multiline: MultilineStyle) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// Changes the labels and colours with which records are written.
+    /// 
+    /// # Arguments
+    /// - `theme`: The `Theme` to write records with.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new theme set.
+    #[inline]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Changes how the columns of the prefix of records are aligned.
+    /// 
+    /// # Arguments
+    /// - `alignment`: The `Alignment` that decides the widths of the columns.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new alignment set.
+    #[inline]
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Changes which thread and process fields are written, for all writers that don't override them.
+    /// 
+    /// # Arguments
+    /// - `fields`: The `PrefixFields` to write.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but with the new fields set.
+    #[inline]
+    pub fn with_fields(mut self, fields: PrefixFields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Makes the logger write asynchronously, i.e., on a dedicated writer thread.
+    /// 
+    /// See `HumanLogger::with_async()` for more information.
+    /// 
+    /// # Arguments
+    /// - `capacity`: The maximum number of records that can be waiting in the queue at once.
+    /// - `overflow`: The `OverflowPolicy` that decides what happens when a record is logged while the queue is full.
+    /// 
+    /// # Returns
+    /// The same HumanLoggerBuilder as `self`, but configured to write asynchronously.
+    #[inline]
+    pub fn with_async(mut self, capacity: usize, overflow: OverflowPolicy) -> Self {
+        self.asynchronous = Some((capacity, overflow));
+        self
+    }
+
+
+
+    /// Builds the HumanLogger.
+    /// 
+    /// Don't forget to also install it at some point using `HumanLogger::init()`, or use [`HumanLoggerBuilder::init()`] to do both at once.
+    /// 
+    /// # Returns
+    /// A new [`HumanLogger`] with the options of this builder.
+    pub fn build(self) -> HumanLogger {
+        // Uptime timestamps count from here
+        timestamp::mark_start();
+
+        // Prepare the settings
+        let gate: Arc<Gate> = Arc::new(Gate::default());
+        let mut config: Config = Config {
+                error_writers : vec![],
+                warn_writers  : vec![],
+                info_writers  : vec![],
+                debug_writers : vec![],
+                trace_writers : vec![],
+
+                debug      : self.mode,
+                directives : self.directives,
+                timestamp  : self.timestamp,
+                multiline  : self.multiline,
+                theme      : self.theme,
+                alignment  : self.alignment,
+                fields     : self.fields,
+
+                gate       : gate.clone(),
+        };
+
+        // Add every writer to the lists of the levels it wants
+        for writer in self.writers.unwrap_or_else(|| vec![ LogWriter::stdout(), LogWriter::stderr() ]) {
+            let filter: LevelSet = writer.filter;
+            let writer: SharedWriter = Arc::new(Mutex::new((WriterState::Enabled, writer.into())));
+            config.add(writer, filter);
+        }
+        config.update_gate();
+        HumanLogger {
+            config       : Arc::new(RwLock::new(config)),
+            gate,
+            asynchronous : self.asynchronous,
+            background   : None,
+        }
+    }
+
+    /// Builds the HumanLogger and initializes it as the `log`-crate's logger.
+    /// 
+    /// # Errors
+    /// This function may error if we failed to setup the logger. This can happen if there already was one or any other reason that `log` crashes.
+    #[inline]
+    pub fn init(self) -> Result<(), SetLoggerError> { self.build().init() }
+
+    /// Builds the HumanLogger and initializes it as the `log`-crate's logger, returning a handle to change it at runtime.
+    /// 
+    /// # Returns
+    /// A [`LoggerHandle`] that can be used to change the installed logger. See `HumanLogger::init_with_handle()` for more information.
+    /// 
+    /// # Errors
+    /// This function may error if we failed to setup the logger. This can happen if there already was one or any other reason that `log` crashes.
+    #[inline]
+    pub fn init_with_handle(self) -> Result<LoggerHandle, SetLoggerError> { self.build().init_with_handle() }
+}
+
+
+
+/// Builds a [`LogWriter`] option by option.
+/// 
+/// Create one with [`LogWriter::builder()`].
+pub struct LogWriterBuilder {
+    /// The writer we're building, which has every option but the colour set already.
+    writer : LogWriter,
+    /// Whether to write with ANSI colours, which is resolved once we're done.
+    colour : ColourChoice,
+}
+impl LogWriterBuilder {
+    /// Changes whether to write with ANSI colours.
+    /// 
+    /// # Arguments
+    /// - `colour`: The `ColourChoice` for this writer. `ColourChoice::Auto` (the default) enables colours if the writer is a terminal and the environment allows it.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new colour choice set.
+    #[inline]
+    pub fn with_colour(mut self, colour: ColourChoice) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Changes which levels are written to this writer.
+    /// 
+    /// # Arguments
+    /// - `levels`: The levels that are only allowed to be written to this writer, e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels like `Level::Error..=Level::Info` (see [`LevelSet`]).
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new levels set.
+    #[inline]
+    pub fn with_levels(mut self, levels: impl Into<LevelSet>) -> Self {
+        self.writer.filter = levels.into();
+        self
+    }
+
+    /// Changes the label of this writer.
+    /// 
+    /// # Arguments
+    /// - `label`: Some description of the writer for debugging purposes, which is also used to find it with a [`LoggerHandle`].
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new label set.
+    #[inline]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.writer.label = label.into();
+        self
+    }
+
+    /// Changes the [`Formatter`] with which this writer writes its messages.
+    /// 
+    /// See `LogWriter::with_formatter()` for more information.
+    /// 
+    /// # Arguments
+    /// - `formatter`: The new `Formatter` to write messages with.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new formatter set.
+    #[inline]
+    pub fn with_formatter(mut self, formatter: impl 'static + Formatter) -> Self {
+        self.writer = self.writer.with_formatter(formatter);
+        self
+    }
+
+    /// Sets `RUST_LOG`-style directives that decide which levels are written to this writer per target.
+    /// 
+    /// See `LogWriter::with_directives()` for more information.
+    /// 
+    /// # Arguments
+    /// - `directives`: The `Directives` to apply to this writer.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new directives set.
+    #[inline]
+    pub fn with_directives(mut self, directives: Directives) -> Self {
+        self.writer = self.writer.with_directives(directives);
+        self
+    }
+
+    /// Changes the style in which this writer writes timestamps, overriding that of the HumanLogger.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The `TimestampStyle` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new timestamp style set.
+    #[inline]
+    pub fn with_timestamp(mut self, timestamp: TimestampStyle) -> Self {
+        self.writer = self.writer.with_timestamp(timestamp);
+        self
+    }
+
+    /// Changes which thread and process fields this writer writes, overriding those of the HumanLogger.
+    /// 
+    /// # Arguments
+    /// - `fields`: The `PrefixFields` to write.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new fields set.
+    #[inline]
+    pub fn with_fields(mut self, fields: PrefixFields) -> Self {
+        self.writer = self.writer.with_fields(fields);
+        self
+    }
+
+    /// Changes what happens when writing to this writer fails.
+    /// 
+    /// See `LogWriter::with_error_policy()` for more information.
+    /// 
+    /// # Arguments
+    /// - `policy`: The `ErrorPolicy` to apply to this writer.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new error policy set.
+    #[inline]
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.writer = self.writer.with_error_policy(policy);
+        self
+    }
+
+    /// Only writes records to this writer for which the given closure returns true.
+    /// 
+    /// See `LogWriter::with_predicate()` for more information.
+    /// 
+    /// # Arguments
+    /// - `predicate`: The closure that decides whether a record is written, based on its `Metadata`.
+    /// 
+    /// # Returns
+    /// The same LogWriterBuilder as `self`, but with the new predicate set.
+    #[inline]
+    pub fn with_predicate(mut self, predicate: impl 'static + Send + Sync + Fn(&Metadata) -> bool) -> Self {
+        self.writer = self.writer.with_predicate(predicate);
+        self
+    }
+
+
+
+    /// Builds the LogWriter.
+    /// 
+    /// # Returns
+    /// A new [`LogWriter`] with the options of this builder, which can be given to a [`HumanLogger`].
+    #[inline]
+    pub fn build(mut self) -> LogWriter {
+        self.writer.colour = self.colour.resolve(self.writer.is_terminal);
+        self.writer
+    }
+}
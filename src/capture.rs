@@ -0,0 +1,290 @@
+//  CAPTURE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:18:58
+//  Last edited:
+//    16 Oct 2026, 15:39:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements capturing log records in tests, either per thread with a
+//!   [`Capture`] or in memory with a [`MemoryWriter`].
+// 
+
+use std::cell::{RefCell, RefMut};
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Once};
+
+use log::{Level, LevelFilter, Record};
+use parking_lot::Mutex;
+
+use crate::scope::{ScopeStack, Snapshot};
+use crate::{DebugMode, FormatContext, Formatter, HumanLogger, LogWriter, PrettyFormatter, TimestampStyle};
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The captures that are active on this thread. Only the last (innermost) one receives records.
+    static CAPTURES: RefCell<Vec<Rc<RefCell<CaptureState>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The number of captures that are active on any thread.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes sure we only attempt to install a logger once.
+static INSTALL: Once = Once::new();
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the level that `log` should at least let through because of active captures.
+/// 
+/// # Returns
+/// `LevelFilter::Trace` if any capture is active on any thread, or else `LevelFilter::Off`.
+#[inline]
+pub(crate) fn active_level() -> LevelFilter {
+    if ACTIVE.load(Ordering::Relaxed) > 0 { LevelFilter::Trace } else { LevelFilter::Off }
+}
+
+/// Returns whether a [`Capture`] is active on the current thread.
+#[inline]
+pub(crate) fn is_capturing() -> bool { CAPTURES.try_with(|captures| !captures.borrow().is_empty()).unwrap_or(false) }
+
+/// Hands the given record to the innermost [`Capture`] on the current thread, if any.
+/// 
+/// # Arguments
+/// - `record`: The record to capture.
+/// - `scopes`: The scopes that were active when the record was logged.
+/// 
+/// # Returns
+/// Whether the record was captured. If so, it should not be written anywhere else.
+pub(crate) fn capture(record: &Record, scopes: &Snapshot) -> bool {
+    CAPTURES.try_with(|captures| {
+        let state: Rc<RefCell<CaptureState>> = match captures.borrow().last() {
+            Some(state) => state.clone(),
+            None        => return false,
+        };
+        let mut state: RefMut<CaptureState> = state.borrow_mut();
+        let stack: ScopeStack = scopes.stack_for(state.id);
+
+        // Render the record without colours or timestamps, so it's easy to assert on
+        let mut text: Vec<u8> = Vec::new();
+        let timestamp: TimestampStyle = TimestampStyle::none();
+        let ctx: FormatContext = FormatContext::new(false, state.mode).with_timestamp(&timestamp).with_scopes(stack);
+        if state.formatter.format(&mut text, record, &ctx).is_err() { text.clear(); }
+
+        state.records.push(CapturedRecord {
+            level   : record.level(),
+            target  : record.target().into(),
+            message : record.args().to_string(),
+            scopes  : stack.to_vec().into_iter().map(String::from).collect(),
+            text    : String::from_utf8_lossy(&text).into_owned(),
+        });
+        scopes.announce(state.id);
+        true
+    }).unwrap_or(false)
+}
+
+/// Updates `log`'s maximum level after the set of active captures changed.
+fn sync_max_level() {
+    let installed: LevelFilter = crate::INSTALLED.get().map(|config| config.read().max_level()).unwrap_or(LevelFilter::Off);
+    log::set_max_level(installed.max(active_level()));
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// A record that was captured by a [`Capture`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CapturedRecord {
+    /// The level of the record.
+    pub level   : Level,
+    /// The target of the record.
+    pub target  : String,
+    /// The message of the record, as given to the logging macro.
+    pub message : String,
+    /// The scopes (see [`scope::enter()`](crate::scope::enter())) that were active when the record was logged, from the outermost to the innermost one.
+    pub scopes  : Vec<String>,
+    /// The record as the capture's formatter rendered it, without colours or timestamps.
+    pub text    : String,
+}
+
+/// The state of a [`Capture`], which is shared with the thread-local list of captures.
+struct CaptureState {
+    /// The records captured so far.
+    records   : Vec<CapturedRecord>,
+    /// The formatter used to render the records.
+    formatter : Box<dyn Formatter>,
+    /// The debug mode to render the records in.
+    mode      : DebugMode,
+    /// The identifier with which scopes remember that they were introduced to this capture.
+    id        : usize,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Captures all records logged on the current thread for as long as it lives.
+/// 
+/// This is meant for tests, which typically run in parallel on separate threads. Because captures are per-thread, every test only sees its own records. Records logged on other threads (e.g., ones spawned by the test) are not captured.
+/// 
+/// While a capture is active, its records are not written to the writers of the logger, and every level is captured regardless of the debug mode or directives. Captures can be nested, in which case only the innermost one receives records.
+/// 
+/// Capturing requires a [`HumanLogger`] to be installed. If no logger has been installed yet when the first Capture is started, one without any writers is installed. If another logger was installed instead, nothing is captured.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::capture::Capture;
+/// use log::{info, warn, Level};
+/// 
+/// let capture: Capture = Capture::start();
+/// info!("Hello there!");
+/// warn!(target: "kenobi", "General Kenobi!");
+/// 
+/// let records = capture.records();
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].level, Level::Info);
+/// assert_eq!(records[1].target, "kenobi");
+/// assert_eq!(records[1].message, "General Kenobi!");
+/// assert_eq!(capture.text(), "[INFO rust_out] Hello there!\n[WARNING rust_out kenobi] General Kenobi!\n");
+/// 
+/// // Other threads have their own captures
+/// std::thread::spawn(|| {
+///     let capture: Capture = Capture::start();
+///     info!("You are a bold one");
+///     assert_eq!(capture.records().len(), 1);
+/// }).join().unwrap();
+/// assert_eq!(capture.records().len(), 2);
+/// ```
+pub struct Capture {
+    /// The state of this capture, which is also in the thread-local list.
+    state : Rc<RefCell<CaptureState>>,
+}
+impl Capture {
+    /// Starts capturing the records logged on the current thread.
+    /// 
+    /// Records are rendered with the [`PrettyFormatter`] in `DebugMode::Debug`, but without colours and timestamps. Use `Capture::with_mode()` or `Capture::with_formatter()` to change this.
+    /// 
+    /// # Returns
+    /// A new Capture that captures records until it is dropped.
+    pub fn start() -> Self {
+        // Make sure there's a logger to pass records to us
+        INSTALL.call_once(|| { let _ = HumanLogger::new(Vec::<LogWriter>::new(), DebugMode::HumanFriendly).init(); });
+
+        // Register ourselves
+        let state: Rc<RefCell<CaptureState>> = Rc::new(RefCell::new(CaptureState { records: vec![], formatter: Box::new(PrettyFormatter), mode: DebugMode::Debug, id: crate::NEXT_WRITER_ID.fetch_add(1, Ordering::Relaxed) }));
+        CAPTURES.with(|captures| captures.borrow_mut().push(state.clone()));
+        ACTIVE.fetch_add(1, Ordering::Relaxed);
+        sync_max_level();
+        Self { state }
+    }
+
+    /// Changes the `DebugMode` in which captured records are rendered.
+    /// 
+    /// # Arguments
+    /// - `mode`: The `DebugMode` to render in.
+    /// 
+    /// # Returns
+    /// The same Capture as `self`, but rendering records in the given mode from now on.
+    #[inline]
+    pub fn with_mode(self, mode: DebugMode) -> Self {
+        self.state.borrow_mut().mode = mode;
+        self
+    }
+
+    /// Changes the [`Formatter`] with which captured records are rendered.
+    /// 
+    /// # Arguments
+    /// - `formatter`: The Formatter to render with.
+    /// 
+    /// # Returns
+    /// The same Capture as `self`, but rendering records with the given formatter from now on.
+    #[inline]
+    pub fn with_formatter(self, formatter: impl 'static + Formatter) -> Self {
+        self.state.borrow_mut().formatter = Box::new(formatter);
+        self
+    }
+
+
+
+    /// Returns the records captured so far.
+    #[inline]
+    pub fn records(&self) -> Vec<CapturedRecord> { self.state.borrow().records.clone() }
+
+    /// Returns the rendered text of all records captured so far.
+    #[inline]
+    pub fn text(&self) -> String { self.state.borrow().records.iter().map(|record| record.text.as_str()).collect() }
+
+    /// Forgets all records captured so far.
+    #[inline]
+    pub fn clear(&self) { self.state.borrow_mut().records.clear(); }
+}
+impl Drop for Capture {
+    fn drop(&mut self) {
+        let _ = CAPTURES.try_with(|captures| captures.borrow_mut().retain(|state| !Rc::ptr_eq(state, &self.state)));
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        sync_max_level();
+    }
+}
+
+
+
+/// A writer that writes to a shared, in-memory buffer.
+/// 
+/// Clones share the same buffer, so one can be given to a [`LogWriter`] while the other is kept to inspect what was written.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter};
+/// use humanlog::capture::MemoryWriter;
+/// use log::{Level, Log as _, Record};
+/// 
+/// // Use the logger directly instead of installing it, so it is private to this test
+/// let memory: MemoryWriter = MemoryWriter::new();
+/// let logger: HumanLogger = HumanLogger::new(vec![ LogWriter::new(memory.clone(), ColourChoice::No, vec![ Level::Error, Level::Warn ], "memory") ], DebugMode::HumanFriendly);
+/// logger.log(&Record::builder().level(Level::Warn).args(format_args!("Hello there!")).build());
+/// 
+/// assert_eq!(memory.contents(), "WARNING: Hello there!\n");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryWriter {
+    /// The buffer written to.
+    buffer : Arc<Mutex<Vec<u8>>>,
+}
+impl MemoryWriter {
+    /// Constructor for the MemoryWriter that starts with an empty buffer.
+    /// 
+    /// # Returns
+    /// A new MemoryWriter.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns everything written so far as a string.
+    #[inline]
+    pub fn contents(&self) -> String { String::from_utf8_lossy(&self.buffer.lock()).into_owned() }
+
+    /// Returns everything written so far as a string, and empties the buffer.
+    #[inline]
+    pub fn take(&self) -> String { String::from_utf8_lossy(&std::mem::take(&mut *self.buffer.lock())).into_owned() }
+}
+impl Write for MemoryWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
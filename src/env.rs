@@ -0,0 +1,211 @@
+//  ENV.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:05:49
+//  Last edited:
+//    16 Oct 2026, 15:26:04
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements constructing a [`HumanLogger`] from environment
+//!   variables, as described by an [`EnvConfig`].
+// 
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use console::style;
+use log::Level;
+
+use crate::{ColourChoice, DebugMode, Directives, HumanLogger, LogWriter, TimestampStyle};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses the value of an environment variable, warning the user if it's invalid.
+/// 
+/// # Arguments
+/// - `lookup`: The function to read environment variables with.
+/// - `var`: The name of the variable to read.
+/// 
+/// # Returns
+/// The parsed value, or [`None`] if the variable was not set or could not be parsed.
+fn parse_var<T>(lookup: &impl Fn(&str) -> Option<String>, var: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw: String = lookup(var)?;
+    match T::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(err)  => {
+            eprintln!("{}: Failed to parse environment variable '{}': {} (ignoring it)", style("WARNING").yellow().bold(), var, err);
+            None
+        },
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The configuration of a [`HumanLogger`] as read from environment variables.
+/// 
+/// See [`HumanLogger::from_env_var()`] for which variables are read. Convert it into a HumanLogger logging to the terminal with `HumanLogger::from()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EnvConfig {
+    /// The `DebugMode` to log with.
+    pub mode       : DebugMode,
+    /// Whether to write with ANSI colours.
+    pub colour     : ColourChoice,
+    /// The style in which to write timestamps.
+    pub timestamp  : TimestampStyle,
+    /// The `RUST_LOG`-style directives to filter records with.
+    pub directives : Directives,
+}
+impl EnvConfig {
+    /// Reads the configuration from the environment variables with the given prefix.
+    /// 
+    /// # Arguments
+    /// - `var`: The name of the variable with the directives, which is also used as prefix for the others.
+    /// - `fallback`: The `DebugMode` to use if the mode variable is not set.
+    /// 
+    /// # Returns
+    /// A new EnvConfig with what the environment says, and defaults for everything it doesn't.
+    #[inline]
+    pub fn from_env_var(var: impl AsRef<str>, fallback: DebugMode) -> Self { Self::from_env_var_with(var, fallback, |name| std::env::var(name).ok()) }
+
+    /// Reads the configuration from something that acts like the environment.
+    /// 
+    /// This is useful to test the configuration without modifying the actual (process-wide) environment.
+    /// 
+    /// # Arguments
+    /// - `var`: The name of the variable with the directives, which is also used as prefix for the others.
+    /// - `fallback`: The `DebugMode` to use if the mode variable is not set.
+    /// - `lookup`: A function that returns the value of the variable with the given name, if any.
+    /// 
+    /// # Returns
+    /// A new EnvConfig with what the environment says, and defaults for everything it doesn't.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use humanlog::{ColourChoice, DebugMode, Directives, EnvConfig, TimestampStyle};
+    /// 
+    /// fn config(env: &[(&str, &str)]) -> EnvConfig {
+    ///     let env: HashMap<&str, &str> = env.iter().copied().collect();
+    ///     EnvConfig::from_env_var_with("MYAPP_LOG", DebugMode::HumanFriendly, |name| env.get(name).map(|value| value.to_string()))
+    /// }
+    /// 
+    /// // Without any variables, everything is left at its default
+    /// let default: EnvConfig = config(&[]);
+    /// assert_eq!(default.mode, DebugMode::HumanFriendly);
+    /// assert_eq!(default.colour, ColourChoice::Auto);
+    /// assert_eq!(default.timestamp, TimestampStyle::default());
+    /// assert_eq!(default.directives, Directives::default());
+    /// 
+    /// // Every setting has its own variable
+    /// let set: EnvConfig = config(&[ ("MYAPP_LOG", "myapp=trace"), ("MYAPP_LOG_MODE", "full"), ("MYAPP_LOG_COLOUR", "never"), ("MYAPP_LOG_TIMESTAMP", "utc") ]);
+    /// assert_eq!(set.mode, DebugMode::Full);
+    /// assert_eq!(set.colour, ColourChoice::No);
+    /// assert_eq!(set.timestamp, TimestampStyle::utc());
+    /// assert_eq!(set.directives, "myapp=trace".parse::<Directives>().unwrap());
+    /// 
+    /// // The colour can be spelled either way, but the British spelling wins
+    /// assert_eq!(config(&[ ("MYAPP_LOG_COLOR", "always") ]).colour, ColourChoice::Yes);
+    /// assert_eq!(config(&[ ("MYAPP_LOG_COLOUR", "never"), ("MYAPP_LOG_COLOR", "always") ]).colour, ColourChoice::No);
+    /// 
+    /// // `RUST_LOG` is only read if the variable itself is not set
+    /// assert_eq!(config(&[ ("RUST_LOG", "hyper=warn") ]).directives, "hyper=warn".parse::<Directives>().unwrap());
+    /// assert_eq!(config(&[ ("MYAPP_LOG", "myapp=trace"), ("RUST_LOG", "hyper=warn") ]).directives, "myapp=trace".parse::<Directives>().unwrap());
+    /// 
+    /// // Invalid values are ignored (with a warning)
+    /// let invalid: EnvConfig = config(&[ ("MYAPP_LOG_MODE", "verbose"), ("MYAPP_LOG_COLOUR", "sometimes"), ("MYAPP_LOG_COLOR", "always"), ("MYAPP_LOG_TIMESTAMP", "yesterday") ]);
+    /// assert_eq!(invalid.mode, DebugMode::HumanFriendly);
+    /// assert_eq!(invalid.colour, ColourChoice::Yes);
+    /// assert_eq!(invalid.timestamp, TimestampStyle::default());
+    /// ```
+    pub fn from_env_var_with(var: impl AsRef<str>, fallback: DebugMode, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let var: &str = var.as_ref();
+        Self {
+            mode       : parse_var(&lookup, &format!("{var}_MODE")).unwrap_or(fallback),
+            colour     : parse_var(&lookup, &format!("{var}_COLOUR")).or_else(|| parse_var(&lookup, &format!("{var}_COLOR"))).unwrap_or(ColourChoice::Auto),
+            timestamp  : parse_var(&lookup, &format!("{var}_TIMESTAMP")).unwrap_or_default(),
+            directives : if lookup(var).is_some() { parse_var(&lookup, var) } else { parse_var(&lookup, "RUST_LOG") }.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<EnvConfig> for HumanLogger {
+    #[inline]
+    fn from(value: EnvConfig) -> Self {
+        Self::new(vec![
+            LogWriter::new(std::io::stdout(), value.colour, vec![ Level::Trace, Level::Debug, Level::Info ], "stdout"),
+            LogWriter::new(std::io::stderr(), value.colour, vec![ Level::Warn, Level::Error ], "stderr"),
+        ], value.mode)
+            .with_directives(value.directives)
+            .with_timestamp(value.timestamp)
+    }
+}
+
+
+
+impl HumanLogger {
+    /// Constructor for the HumanLogger that prepares it for logging to the terminal, configured by the `HUMANLOG*` environment variables.
+    /// 
+    /// This is equivalent to calling `HumanLogger::from_env_var("HUMANLOG", fallback)`; see its documentation for which variables are read.
+    /// 
+    /// Don't forget to also install the Logger at some point using `HumanLogger::init()`.
+    /// 
+    /// # Arguments
+    /// - `fallback`: The `DebugMode` to use if `HUMANLOG_MODE` is not set.
+    /// 
+    /// # Returns
+    /// A new HumanLogger that will log to stdout and stderr.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger};
+    /// 
+    /// // Operators can now use, e.g., `HUMANLOG_MODE=full HUMANLOG=hyper=warn` to debug an already shipped binary
+    /// if let Err(err) = HumanLogger::from_env(DebugMode::HumanFriendly).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_env(fallback: DebugMode) -> Self { Self::from_env_var("HUMANLOG", fallback) }
+
+    /// Constructor for the HumanLogger that prepares it for logging to the terminal, configured by environment variables with a custom prefix.
+    /// 
+    /// Given a `var` of, e.g., `MYAPP_LOG`, the following variables are read:
+    /// - `MYAPP_LOG`: `RUST_LOG`-style [`Directives`] (e.g., `myapp=trace,hyper=warn,info`). If not set, `RUST_LOG` is read instead.
+    /// - `MYAPP_LOG_MODE`: The [`DebugMode`] (e.g., `friendly`, `debug`, `full` or `0`-`2`). If not set, `fallback` is used.
+    /// - `MYAPP_LOG_COLOUR` (or `MYAPP_LOG_COLOR`): The [`ColourChoice`] (`auto`, `always` or `never`). If not set, `auto` is used.
+    /// - `MYAPP_LOG_TIMESTAMP`: The [`TimestampStyle`] (e.g., `local`, `utc`, `+02:00`, `unix`, `unix-millis`, `uptime` or `none`). If not set, `local` is used.
+    /// 
+    /// Variables with invalid values are ignored with a warning on stderr. Use [`EnvConfig`] to inspect what was read.
+    /// 
+    /// Don't forget to also install the Logger at some point using `HumanLogger::init()`.
+    /// 
+    /// # Arguments
+    /// - `var`: The name of the variable with the directives, which is also used as prefix for the others.
+    /// - `fallback`: The `DebugMode` to use if the mode variable is not set.
+    /// 
+    /// # Returns
+    /// A new HumanLogger that will log to stdout and stderr.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger};
+    /// 
+    /// // Run with, e.g., `MYAPP_LOG_MODE=debug MYAPP_LOG_TIMESTAMP=utc` for debug output with UTC timestamps
+    /// if let Err(err) = HumanLogger::from_env_var("MYAPP_LOG", DebugMode::HumanFriendly).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_env_var(var: impl AsRef<str>, fallback: DebugMode) -> Self { EnvConfig::from_env_var(var, fallback).into() }
+}
@@ -0,0 +1,181 @@
+//  FIELDS.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:36:43
+//  Last edited:
+//    16 Oct 2026, 15:36:43
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the [`PrefixFields`], which decide what is written about the
+//!   thread and process that logged a record, together with the functions
+//!   that find this information.
+// 
+
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The name of the current thread, or its ID if it has none.
+    static THREAD: Rc<str> = {
+        let thread: std::thread::Thread = std::thread::current();
+        match thread.name() {
+            Some(name) => name.into(),
+            None       => format!("{:?}", thread.id()).into(),
+        }
+    };
+}
+
+/// The name of the machine we're running on, once found.
+static HOSTNAME: OnceLock<Option<String>> = OnceLock::new();
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the name of the machine we're running on.
+/// 
+/// # Returns
+/// The hostname, or [`None`] if it could not be found.
+#[cfg(unix)]
+fn find_hostname() -> Option<String> {
+    let mut buf: [u8; 256] = [0; 256];
+    // SAFETY: We pass a valid buffer together with its length, and leave room for the terminating null byte
+    if unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len() - 1) } != 0 { return None; }
+    let len: usize = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    String::from_utf8(buf[..len].to_vec()).ok().filter(|name| !name.is_empty())
+}
+#[cfg(not(unix))]
+fn find_hostname() -> Option<String> { std::env::var("COMPUTERNAME").ok().filter(|name| !name.is_empty()) }
+
+/// Returns the name of the machine we're running on.
+/// 
+/// It is only looked up once, so later changes to the hostname are not picked up.
+/// 
+/// # Returns
+/// The hostname, or [`None`] if it could not be found.
+#[inline]
+pub(crate) fn hostname() -> Option<&'static str> { HOSTNAME.get_or_init(find_hostname).as_deref() }
+
+/// Returns the name of the current thread.
+/// 
+/// # Returns
+/// The name given to the thread when it was spawned, or its [`ThreadId`](std::thread::ThreadId) (e.g., `ThreadId(2)`) if it has none.
+#[inline]
+pub(crate) fn thread() -> Rc<str> {
+    THREAD.try_with(Rc::clone).unwrap_or_else(|_| format!("{:?}", std::thread::current().id()).into())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Decides what is written about the thread and process that logged a record.
+/// 
+/// In the [`DebugFormatter`](crate::DebugFormatter) and [`FullFormatter`](crate::FullFormatter), every enabled field is written (dimmed) between the timestamp and the level. The [`JsonFormatter`](crate::JsonFormatter) writes them as the `hostname`, `pid` and `thread` fields, the [`SyslogFormatter`](crate::SyslogFormatter) adds the thread as a `thread` parameter (syslog always includes the hostname and process ID), and the `JournaldFormatter` adds the `TID` and `THREAD_NAME` fields (journald records the hostname and process ID by itself). The [`HumanFriendlyFormatter`](crate::HumanFriendlyFormatter) ignores them.
+/// 
+/// By default, no fields are written.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugFormatter, DebugMode, FormatContext, Formatter as _, PrefixFields, TimestampStyle};
+/// use log::{Level, Record};
+/// 
+/// std::thread::Builder::new().name("worker-1".into()).spawn(|| {
+///     let timestamp: TimestampStyle = TimestampStyle::none();
+///     let ctx: FormatContext = FormatContext::new(false, DebugMode::Debug).with_timestamp(&timestamp).with_fields(PrefixFields::none().with_thread(true));
+/// 
+///     let mut buf: Vec<u8> = Vec::new();
+///     DebugFormatter.format(&mut buf, &Record::builder().level(Level::Info).target("app").args(format_args!("Hello there!")).build(), &ctx).unwrap();
+///     assert_eq!(String::from_utf8(buf).unwrap(), "[worker-1 INFO app] Hello there!\n");
+/// }).unwrap().join().unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PrefixFields {
+    /// Whether to write the name (or ID) of the thread.
+    thread   : bool,
+    /// Whether to write the process ID.
+    pid      : bool,
+    /// Whether to write the hostname.
+    hostname : bool,
+}
+impl PrefixFields {
+    /// Constructor for the PrefixFields that writes none of the fields.
+    /// 
+    /// This is the default.
+    /// 
+    /// # Returns
+    /// A new PrefixFields with every field disabled.
+    #[inline]
+    pub const fn none() -> Self { Self { thread: false, pid: false, hostname: false } }
+
+    /// Constructor for the PrefixFields that writes all of the fields.
+    /// 
+    /// # Returns
+    /// A new PrefixFields with the thread, process ID and hostname enabled.
+    #[inline]
+    pub const fn all() -> Self { Self { thread: true, pid: true, hostname: true } }
+
+
+
+    /// Changes whether to write the name of the thread that logged a record.
+    /// 
+    /// Threads without a name are written as their [`ThreadId`](std::thread::ThreadId) (e.g., `ThreadId(2)`).
+    /// 
+    /// # Arguments
+    /// - `enabled`: Whether to write the thread.
+    /// 
+    /// # Returns
+    /// The same PrefixFields as `self`, but with the thread enabled or disabled.
+    #[inline]
+    pub const fn with_thread(mut self, enabled: bool) -> Self {
+        self.thread = enabled;
+        self
+    }
+
+    /// Changes whether to write the ID of the process that logged a record.
+    /// 
+    /// # Arguments
+    /// - `enabled`: Whether to write the process ID.
+    /// 
+    /// # Returns
+    /// The same PrefixFields as `self`, but with the process ID enabled or disabled.
+    #[inline]
+    pub const fn with_pid(mut self, enabled: bool) -> Self {
+        self.pid = enabled;
+        self
+    }
+
+    /// Changes whether to write the name of the machine that logged a record.
+    /// 
+    /// # Arguments
+    /// - `enabled`: Whether to write the hostname.
+    /// 
+    /// # Returns
+    /// The same PrefixFields as `self`, but with the hostname enabled or disabled.
+    #[inline]
+    pub const fn with_hostname(mut self, enabled: bool) -> Self {
+        self.hostname = enabled;
+        self
+    }
+
+
+
+    /// Returns whether the thread is written.
+    #[inline]
+    pub const fn thread(&self) -> bool { self.thread }
+
+    /// Returns whether the process ID is written.
+    #[inline]
+    pub const fn pid(&self) -> bool { self.pid }
+
+    /// Returns whether the hostname is written.
+    #[inline]
+    pub const fn hostname(&self) -> bool { self.hostname }
+}
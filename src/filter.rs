@@ -0,0 +1,353 @@
+//  FILTER.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:04:14
+//  Last edited:
+//    16 Oct 2026, 15:57:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines [`Directives`], which can be used to filter log records
+//!   based on their target in the same way as `RUST_LOG` does, and the
+//!   [`LevelSet`], which decides which levels a writer writes.
+// 
+
+use std::borrow::Cow;
+use std::collections::{BinaryHeap, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use log::{Level, LevelFilter};
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when parsing [`Directives`].
+#[derive(Debug)]
+pub enum DirectiveParseError {
+    /// A directive had an empty target (e.g., `=info`).
+    EmptyTarget { directive: String },
+    /// A directive had a level we did not recognize.
+    UnknownLevel { directive: String, level: String },
+}
+impl Display for DirectiveParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use DirectiveParseError::*;
+        match self {
+            EmptyTarget { directive }         => write!(f, "Directive '{directive}' has an empty target"),
+            UnknownLevel { directive, level } => write!(f, "Unknown level '{level}' in directive '{directive}' (expected one of 'off', 'error', 'warn', 'info', 'debug' or 'trace')"),
+        }
+    }
+}
+impl Error for DirectiveParseError {}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A set of `RUST_LOG`-style directives that determine which levels are logged per target.
+/// 
+/// Directives are written as a comma-separated list, where every element is either:
+/// - `target=level`, which sets the maximum level for `target` and all its submodules;
+/// - `level`, which sets the default maximum level for targets not matched by any other directive; or
+/// - `target`, which enables all levels for `target` and all its submodules.
+/// 
+/// Levels are one of `off`, `error`, `warn`, `info`, `debug` or `trace` (case-insensitive).
+/// 
+/// Targets match on path boundaries, i.e., `hyper` matches the targets `hyper` and `hyper::client`, but not `hyperx`. If multiple directives match, the longest (most specific) one wins.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::Directives;
+/// use log::LevelFilter;
+/// 
+/// let directives: Directives = "myapp=trace,hyper=warn,info".parse().unwrap();
+/// assert_eq!(directives.level_for("myapp::server"), Some(LevelFilter::Trace));
+/// assert_eq!(directives.level_for("hyper::client"), Some(LevelFilter::Warn));
+/// assert_eq!(directives.level_for("rustls"), Some(LevelFilter::Info));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Directives {
+    /// The level to apply to targets that are not matched by any directive, if any.
+    default : Option<LevelFilter>,
+    /// The per-target levels, ordered from the longest target to the shortest.
+    targets : Vec<(String, LevelFilter)>,
+}
+impl Directives {
+    /// Constructor for Directives that does not contain any directive yet.
+    /// 
+    /// # Returns
+    /// A new Directives that does not match anything.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Sets the default level for targets that are not matched by any other directive.
+    /// 
+    /// # Arguments
+    /// - `level`: The default maximum `LevelFilter` to apply.
+    /// 
+    /// # Returns
+    /// The same Directives as `self`, but with the default set.
+    #[inline]
+    pub fn with_default(mut self, level: LevelFilter) -> Self {
+        self.default = Some(level);
+        self
+    }
+
+    /// Adds a directive for the given target.
+    /// 
+    /// If there already is a directive for exactly this target, it is overwritten.
+    /// 
+    /// # Arguments
+    /// - `target`: The target (or target prefix) to set the level for.
+    /// - `level`: The maximum `LevelFilter` to apply to the target.
+    /// 
+    /// # Returns
+    /// The same Directives as `self`, but with the directive added.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::Directives;
+    /// use log::LevelFilter;
+    /// 
+    /// let directives: Directives = Directives::new().with_target("hyper", LevelFilter::Warn).with_target("hyper::proto", LevelFilter::Off);
+    /// assert_eq!(directives.level_for("hyper::client"), Some(LevelFilter::Warn));
+    /// assert_eq!(directives.level_for("hyper::proto::h1"), Some(LevelFilter::Off));
+    /// assert_eq!(directives.level_for("myapp"), None);
+    /// ```
+    pub fn with_target(mut self, target: impl Into<String>, level: LevelFilter) -> Self {
+        let target: String = target.into();
+        self.targets.retain(|(t, _)| *t != target);
+        self.targets.push((target, level));
+        self.targets.sort_by_key(|(t, _)| std::cmp::Reverse(t.len()));
+        self
+    }
+
+
+
+    /// Returns the level that these directives assign to the given target.
+    /// 
+    /// # Arguments
+    /// - `target`: The target of a log record (see `log::Metadata::target()`).
+    /// 
+    /// # Returns
+    /// The maximum `LevelFilter` for that target, or [`None`] if no directive matches it and there is no default level either.
+    pub fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        for (prefix, level) in &self.targets {
+            // Match on path boundaries only
+            if let Some(rest) = target.strip_prefix(prefix.as_str()) {
+                if rest.is_empty() || rest.starts_with("::") {
+                    return Some(*level);
+                }
+            }
+        }
+        self.default
+    }
+
+    /// Returns the targets for which these directives set a level.
+    #[inline]
+    pub(crate) fn targets(&self) -> impl Iterator<Item = &str> { self.targets.iter().map(|(target, _)| target.as_str()) }
+
+    /// Returns the default level of these directives, if any.
+    #[inline]
+    pub fn default_level(&self) -> Option<LevelFilter> { self.default }
+
+    /// Returns the most verbose level that any of these directives enables.
+    /// 
+    /// # Returns
+    /// The maximum `LevelFilter` over all directives, or `LevelFilter::Off` if there are none.
+    #[inline]
+    pub fn max_level(&self) -> LevelFilter {
+        self.targets.iter().map(|(_, level)| *level).chain(self.default).max().unwrap_or(LevelFilter::Off)
+    }
+
+    /// Returns whether these directives are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.default.is_none() && self.targets.is_empty() }
+}
+
+impl FromStr for Directives {
+    type Err = DirectiveParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut directives: Self = Self::new();
+        for directive in s.split(',') {
+            let directive: &str = directive.trim();
+            if directive.is_empty() { continue; }
+
+            // Split on the equals-sign, if any
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let (target, level): (&str, &str) = (target.trim(), level.trim());
+                    if target.is_empty() { return Err(DirectiveParseError::EmptyTarget { directive: directive.into() }); }
+                    match LevelFilter::from_str(level) {
+                        Ok(level) => { directives = directives.with_target(target, level); },
+                        Err(_)    => { return Err(DirectiveParseError::UnknownLevel { directive: directive.into(), level: level.into() }); },
+                    }
+                },
+
+                // Either a bare level or a bare target
+                None => match LevelFilter::from_str(directive) {
+                    Ok(level) => { directives.default = Some(level); },
+                    Err(_)    => { directives = directives.with_target(directive, LevelFilter::Trace); },
+                },
+            }
+        }
+        Ok(directives)
+    }
+}
+
+
+
+/// A set of levels that a [`LogWriter`](crate::LogWriter) writes.
+/// 
+/// It can be created from a single `Level`, a `LevelFilter` (which includes every level up to and including it), a range of levels (in either order, e.g., `Level::Warn..=Level::Info` or `Level::Info..=Level::Warn`), or a list of levels (anything that converts into a `Vec<Level>`, e.g., a slice, array, `&Vec<Level>`, `Box<[Level]>` or `VecDeque<Level>`). Levels that are given multiple times are only included once.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::LevelSet;
+/// use log::{Level, LevelFilter};
+/// 
+/// assert_eq!(LevelSet::from(LevelFilter::Info), LevelSet::from([ Level::Error, Level::Warn, Level::Info ]));
+/// assert_eq!(LevelSet::from(Level::Info..=Level::Warn), LevelSet::from(Level::Warn..=Level::Info));
+/// assert_eq!(LevelSet::from(vec![ Level::Info, Level::Info ]).iter().count(), 1);
+/// assert!(!LevelSet::from(Level::Error..=Level::Warn).contains(Level::Info));
+/// 
+/// let levels: Vec<Level> = vec![ Level::Error, Level::Warn ];
+/// assert_eq!(LevelSet::from(&levels), LevelSet::from(levels.clone().into_boxed_slice()));
+/// assert_eq!(LevelSet::from(&levels), LevelSet::from(std::collections::VecDeque::from(levels)));
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct LevelSet {
+    /// The levels in the set, where bit `n` is set if the level with `n + 1` as `usize` is in it.
+    bits : u8,
+}
+impl LevelSet {
+    /// Constructor for the LevelSet that contains no levels.
+    /// 
+    /// # Returns
+    /// A new, empty LevelSet.
+    #[inline]
+    pub const fn empty() -> Self { Self { bits: 0 } }
+
+    /// Constructor for the LevelSet that contains all levels.
+    /// 
+    /// # Returns
+    /// A new LevelSet with `Level::Error` up to and including `Level::Trace`.
+    #[inline]
+    pub const fn all() -> Self { Self { bits: 0b11111 } }
+
+
+
+    /// Adds a level to this set.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` to add. Nothing happens if it's already in the set.
+    /// 
+    /// # Returns
+    /// The same LevelSet as `self`, but with the level added.
+    #[inline]
+    pub const fn with(mut self, level: Level) -> Self {
+        self.bits |= 1 << (level as usize - 1);
+        self
+    }
+
+    /// Removes a level from this set.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` to remove. Nothing happens if it's not in the set.
+    /// 
+    /// # Returns
+    /// The same LevelSet as `self`, but with the level removed.
+    #[inline]
+    pub const fn without(mut self, level: Level) -> Self {
+        self.bits &= !(1 << (level as usize - 1));
+        self
+    }
+
+
+
+    /// Returns whether the given level is in this set.
+    #[inline]
+    pub const fn contains(&self, level: Level) -> bool { self.bits & (1 << (level as usize - 1)) != 0 }
+
+    /// Returns whether this set contains no levels.
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.bits == 0 }
+
+    /// Returns the levels in this set, from the least to the most verbose.
+    #[inline]
+    pub fn iter(&self) -> impl '_ + Iterator<Item = Level> { Level::iter().filter(|level| self.contains(*level)) }
+}
+impl Debug for LevelSet {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { f.debug_set().entries(self.iter()).finish() }
+}
+
+impl From<Level> for LevelSet {
+    #[inline]
+    fn from(value: Level) -> Self { Self::empty().with(value) }
+}
+impl From<LevelFilter> for LevelSet {
+    #[inline]
+    fn from(value: LevelFilter) -> Self { Level::iter().filter(|level| *level <= value).collect() }
+}
+impl From<RangeInclusive<Level>> for LevelSet {
+    #[inline]
+    fn from(value: RangeInclusive<Level>) -> Self {
+        let (start, end): (Level, Level) = (*value.start(), *value.end());
+        Level::iter().filter(|level| start.min(end) <= *level && *level <= start.max(end)).collect()
+    }
+}
+impl From<&[Level]> for LevelSet {
+    #[inline]
+    fn from(value: &[Level]) -> Self { value.iter().copied().collect() }
+}
+impl From<&mut [Level]> for LevelSet {
+    #[inline]
+    fn from(value: &mut [Level]) -> Self { value.iter().copied().collect() }
+}
+impl<const N: usize> From<[Level; N]> for LevelSet {
+    #[inline]
+    fn from(value: [Level; N]) -> Self { value.into_iter().collect() }
+}
+impl<const N: usize> From<&[Level; N]> for LevelSet {
+    #[inline]
+    fn from(value: &[Level; N]) -> Self { value.iter().copied().collect() }
+}
+impl<const N: usize> From<&mut [Level; N]> for LevelSet {
+    #[inline]
+    fn from(value: &mut [Level; N]) -> Self { value.iter().copied().collect() }
+}
+impl From<Vec<Level>> for LevelSet {
+    #[inline]
+    fn from(value: Vec<Level>) -> Self { value.into_iter().collect() }
+}
+impl From<&Vec<Level>> for LevelSet {
+    #[inline]
+    fn from(value: &Vec<Level>) -> Self { value.iter().copied().collect() }
+}
+impl From<Box<[Level]>> for LevelSet {
+    #[inline]
+    fn from(value: Box<[Level]>) -> Self { value.into_vec().into_iter().collect() }
+}
+impl From<Cow<'_, [Level]>> for LevelSet {
+    #[inline]
+    fn from(value: Cow<'_, [Level]>) -> Self { value.iter().copied().collect() }
+}
+impl From<VecDeque<Level>> for LevelSet {
+    #[inline]
+    fn from(value: VecDeque<Level>) -> Self { value.into_iter().collect() }
+}
+impl From<BinaryHeap<Level>> for LevelSet {
+    #[inline]
+    fn from(value: BinaryHeap<Level>) -> Self { value.into_iter().collect() }
+}
+impl FromIterator<Level> for LevelSet {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Level>>(iter: T) -> Self { iter.into_iter().fold(Self::empty(), Self::with) }
+}
@@ -0,0 +1,1092 @@
+//  FORMATTER.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:02:51
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the [`Formatter`]-trait, which decides how a log record is
+//!   written to a [`LogWriter`](crate::LogWriter), together with the
+//!   built-in layouts.
+// 
+
+use std::error::Error;
+use std::fmt::{Arguments, Display, Formatter as FFormatter, Result as FResult, Write as _};
+use std::io::Write;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use console::{measure_text_width, Style};
+use log::kv::{self, Key, Value, VisitSource, VisitValue};
+use log::{Level, Record};
+
+use crate::fields::{self, PrefixFields};
+use crate::scope::ScopeStack;
+use crate::theme::{paint, Painter, Theme};
+use crate::timestamp::TimestampStyle;
+use crate::DebugMode;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Writes the given string as a double-quoted string literal, escaping anything that would otherwise confuse a parser.
+/// 
+/// The escaping scheme is compatible with that of JSON strings, so tooling can parse the values back.
+/// 
+/// # Arguments
+/// - `f`: The formatter to write to.
+/// - `value`: The string to write quoted.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given formatter.
+fn write_quoted(f: &mut FFormatter<'_>, value: &str) -> FResult {
+    f.write_char('"')?;
+    Escaper(f).write_str(value)?;
+    f.write_char('"')
+}
+
+/// Returns the visible width of a single character.
+#[inline]
+fn char_width(c: char) -> usize { measure_text_width(c.encode_utf8(&mut [0; 4])) }
+
+/// Writes the level of a record, styled according to the given [`Theme`].
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `level`: The `Level` to write.
+/// - `theme`: The `Theme` that decides the label and style of the level.
+/// - `width`: If given, the label is padded with spaces until it is (at least) this many characters wide.
+/// - `colour`: Whether to write with ANSI colours.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_level(writer: &mut dyn Write, level: Level, theme: &Theme, width: Option<usize>, colour: bool) -> std::io::Result<()> {
+    let label: &str = theme.label(level);
+    write!(writer, "{}", paint(theme.level_painter(level), colour, label))?;
+    if let Some(width) = width {
+        write!(writer, "{:pad$}", "", pad = width.saturating_sub(measure_text_width(label)))?;
+    }
+    Ok(())
+}
+
+/// Writes the enabled thread and process fields of a record, each followed by a space.
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `fields`: The `PrefixFields` that decide which fields to write.
+/// - `theme`: The `Theme` that decides the style of the fields.
+/// - `colour`: Whether to write with ANSI colours.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_fields(writer: &mut dyn Write, fields: PrefixFields, theme: &Theme, colour: bool) -> std::io::Result<()> {
+    if fields.hostname() {
+        if let Some(hostname) = fields::hostname() { write!(writer, "{} ", paint(theme.fields_painter(), colour, hostname))?; }
+    }
+    if fields.pid() {
+        write!(writer, "{} ", paint(theme.fields_painter(), colour, std::process::id()))?;
+    }
+    if fields.thread() {
+        write!(writer, "{} ", paint(theme.fields_painter(), colour, fields::thread()))?;
+    }
+    Ok(())
+}
+
+/// Writes the scopes of a record as a colon-separated list, followed by a colon and a space.
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `scopes`: The `ScopeStack` to write. If it's empty, nothing is written.
+/// - `theme`: The `Theme` that decides the style of the scopes.
+/// - `colour`: Whether to write with ANSI colours.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_scopes(writer: &mut dyn Write, scopes: ScopeStack, theme: &Theme, colour: bool) -> std::io::Result<()> {
+    let mut res: std::io::Result<()> = Ok(());
+    scopes.for_each(|text| {
+        if res.is_ok() { res = write!(writer, "{}:", paint(theme.scope_painter(), colour, text)); }
+    });
+    res?;
+    if !scopes.is_empty() { writer.write_all(b" ")?; }
+    Ok(())
+}
+
+/// Writes the scopes of a record that no earlier record introduced as headers, each on its own line and indented by its depth.
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `scopes`: The `ScopeStack` to introduce.
+/// - `theme`: The `Theme` that decides the style of the scopes.
+/// - `colour`: Whether to write with ANSI colours.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_scope_headers(writer: &mut dyn Write, scopes: ScopeStack, theme: &Theme, colour: bool) -> std::io::Result<()> {
+    let mut res: std::io::Result<()> = Ok(());
+    let mut depth: usize = 0;
+    scopes.for_each(|text| {
+        if res.is_ok() && depth >= scopes.announced() {
+            res = writeln!(writer, "{:indent$}{}:", "", paint(theme.scope_painter(), colour, text), indent = 2 * depth);
+        }
+        depth += 1;
+    });
+    res
+}
+
+/// Writes the `:line` suffix of a location into the given buffer, so it can be written as part of a column without allocating.
+/// 
+/// # Arguments
+/// - `buf`: The buffer to write to, which fits the colon and any `u32`.
+/// - `line`: The line number to write.
+/// 
+/// # Returns
+/// The part of the buffer that was written, e.g., `:42`.
+fn write_line(buf: &mut [u8; 11], line: u32) -> &str {
+    let mut rest: &mut [u8] = &mut buf[..];
+    // The buffer is large enough for any line number, so this never fails
+    let _ = write!(rest, ":{line}");
+    let len: usize = 11 - rest.len();
+    std::str::from_utf8(&buf[..len]).unwrap_or("")
+}
+
+/// Writes a column of the prefix of a record, which consists of one or more differently styled parts.
+/// 
+/// If a width is given, the column is padded with spaces until it is exactly that wide, or elided from the left (e.g., `…ver::http`) if it is wider. We elide from the left because the end of a target or location is usually the most telling part. Widths are measured in visible characters, so styles don't affect them.
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `parts`: The parts of the column, each with the `Painter` to write it in. Empty parts are skipped.
+/// - `width`: The width to pad or elide the column to, if any.
+/// - `colour`: Whether to write with ANSI colours.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_column(writer: &mut dyn Write, parts: &[(&str, &Painter)], width: Option<usize>, colour: bool) -> std::io::Result<()> {
+    let total: usize = parts.iter().map(|(text, _)| measure_text_width(text)).sum();
+    let width: usize = match width {
+        Some(width) if total > width => width,
+        width => {
+            for (text, style) in parts.iter().filter(|(text, _)| !text.is_empty()) {
+                write!(writer, "{}", paint(style, colour, text))?;
+            }
+            return write!(writer, "{:pad$}", "", pad = width.unwrap_or(total).saturating_sub(total));
+        },
+    };
+
+    // Skip characters until the rest fits together with the ellipsis
+    let mut skip: usize = total - width + 1;
+    let mut written: usize = 0;
+    for (text, style) in parts {
+        let mut rest: &str = text;
+        while skip > 0 {
+            let Some(c) = rest.chars().next() else { break; };
+            skip = skip.saturating_sub(char_width(c));
+            rest = &rest[c.len_utf8()..];
+        }
+        if skip > 0 { continue; }
+
+        // Write the ellipsis in the style of the part we cut in
+        if written == 0 {
+            write!(writer, "{}", paint(style, colour, '\u{2026}'))?;
+            written += 1;
+        }
+        if !rest.is_empty() {
+            write!(writer, "{}", paint(style, colour, rest))?;
+            written += measure_text_width(rest);
+        }
+    }
+    write!(writer, "{:pad$}", "", pad = width.saturating_sub(written))
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Wrapper around a [`Formatter`](FFormatter) that escapes everything written to it such that it can be safely embedded in a (JSON-compatible) string literal.
+/// 
+/// Next to quotes, backslashes and control characters, this also escapes the Unicode line- and paragraph separators (U+2028 and U+2029), as some tools treat those as line endings.
+struct Escaper<'f1, 'f2>(&'f1 mut FFormatter<'f2>);
+impl<'f1, 'f2> std::fmt::Write for Escaper<'f1, 'f2> {
+    fn write_str(&mut self, s: &str) -> FResult {
+        for c in s.chars() {
+            match c {
+                '"'  => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+
+/// How far a [`Measure`] is in an ANSI escape sequence.
+#[derive(Clone, Copy)]
+enum Escape {
+    /// Not in an escape sequence.
+    Text,
+    /// Just after the `ESC` that starts an escape sequence.
+    Started,
+    /// In a control sequence (`ESC [`), which ends with a byte in `@`-`~`.
+    Csi,
+}
+
+/// Wrapper around a [`Write`]r that measures the visible width of everything written to it, skipping ANSI escape sequences.
+/// 
+/// This allows the prefix of a record to be written straight to the writer while still knowing how wide it is.
+struct Measure<'w> {
+    /// The writer to write to.
+    writer  : &'w mut dyn Write,
+    /// The visible width of everything written so far, or [`None`] if it's not measured.
+    width   : Option<usize>,
+    /// How far we are in an ANSI escape sequence.
+    escape  : Escape,
+    /// The bytes of a character that was only partially written so far, and how many of them there are.
+    partial : ([u8; 4], usize),
+}
+impl<'w> Measure<'w> {
+    /// Constructor for the Measure.
+    /// 
+    /// # Arguments
+    /// - `writer`: The `Write`r to write to.
+    /// - `measure`: Whether to actually measure the width of what is written. If not, this simply passes everything on.
+    /// 
+    /// # Returns
+    /// A new Measure that has measured nothing yet.
+    #[inline]
+    fn new(writer: &'w mut dyn Write, measure: bool) -> Self { Self { writer, width: if measure { Some(0) } else { None }, escape: Escape::Text, partial: ([0; 4], 0) } }
+
+    /// Returns the visible width of everything written so far, or `0` if it's not measured.
+    #[inline]
+    fn width(&self) -> usize { self.width.unwrap_or(0) }
+
+    /// Feeds the next written byte to the measurement.
+    /// 
+    /// # Arguments
+    /// - `byte`: The byte that was written.
+    /// 
+    /// # Returns
+    /// The visible width that the byte added, which is non-zero only if it completes a character outside of an escape sequence.
+    fn feed(&mut self, byte: u8) -> usize {
+        match (self.escape, byte) {
+            (Escape::Text, 0x1B)          => { self.escape = Escape::Started; 0 },
+            (Escape::Started, b'[')       => { self.escape = Escape::Csi; 0 },
+            (Escape::Started, _)          => { self.escape = Escape::Text; 0 },
+            (Escape::Csi, 0x40..=0x7E)    => { self.escape = Escape::Text; 0 },
+            (Escape::Csi, _)              => 0,
+            (Escape::Text, _)             => {
+                let (bytes, len): &mut ([u8; 4], usize) = &mut self.partial;
+                bytes[*len] = byte;
+                *len += 1;
+                match std::str::from_utf8(&bytes[..*len]) {
+                    Ok(text) => { *len = 0; text.chars().map(char_width).sum() },
+                    // Wait for the rest of the character
+                    Err(err) if err.error_len().is_none() && *len < 4 => 0,
+                    Err(_) => { *len = 0; 0 },
+                }
+            },
+        }
+    }
+}
+impl<'w> Write for Measure<'w> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n: usize = self.writer.write(buf)?;
+        if self.width.is_some() {
+            let added: usize = buf[..n].iter().map(|byte| self.feed(*byte)).sum();
+            self.width = self.width.map(|width| width + added);
+        }
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> { self.writer.flush() }
+}
+
+
+
+/// Wrapper around a [`Formatter`](FFormatter) that lays out the continuation lines of everything written to it according to a [`MultilineStyle`].
+struct Continuation<'f1, 'f2> {
+    /// The formatter to write to.
+    f      : &'f1 mut FFormatter<'f2>,
+    /// How to lay out continuation lines.
+    style  : MultilineStyle,
+    /// The visible width of the prefix that continuation lines should align with.
+    width  : usize,
+    /// Whether to write with ANSI colours.
+    colour : bool,
+}
+impl<'f1, 'f2> std::fmt::Write for Continuation<'f1, 'f2> {
+    fn write_str(&mut self, s: &str) -> FResult {
+        let mut lines = s.split('\n');
+        if let Some(line) = lines.next() { self.f.write_str(line)?; }
+        for line in lines {
+            self.f.write_char('\n')?;
+            match self.style {
+                MultilineStyle::Verbatim => {},
+                MultilineStyle::Indent   => write!(self.f, "{:width$}", "", width = self.width)?,
+                MultilineStyle::Gutter   => write!(self.f, "{:width$}{} ", "", Style::new().force_styling(self.colour).dim().apply_to('\u{2502}'), width = self.width.saturating_sub(2))?,
+            }
+            self.f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the message of a record such that its continuation lines are laid out according to a [`MultilineStyle`].
+struct DisplayMessage<'a, 'b> {
+    /// The message to write.
+    args   : &'a Arguments<'b>,
+    /// How to lay out continuation lines.
+    style  : MultilineStyle,
+    /// The visible width of the prefix that continuation lines should align with.
+    width  : usize,
+    /// Whether to write with ANSI colours.
+    colour : bool,
+}
+impl<'a, 'b> Display for DisplayMessage<'a, 'b> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        if self.style == MultilineStyle::Verbatim { return write!(f, "{}", self.args); }
+        write!(Continuation { f, style: self.style, width: self.width, colour: self.colour }, "{}", self.args)
+    }
+}
+
+
+
+/// Formats a key/value [`Value`] such that its type is preserved.
+/// 
+/// Concretely, strings (and anything captured by `Display` or `Debug`) are written quoted, while numbers, booleans and nulls are written bare.
+struct DisplayValue<'a, 'v> {
+    /// The value to write.
+    value : &'a Value<'v>,
+    /// Whether to stick to strict JSON, which means that non-finite floats are written as `null`.
+    json  : bool,
+}
+impl<'a, 'v> Display for DisplayValue<'a, 'v> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        /// Visitor that writes the value to a formatter.
+        struct Visitor<'f1, 'f2> {
+            f    : &'f1 mut FFormatter<'f2>,
+            json : bool,
+        }
+        impl<'f1, 'f2, 'v> VisitValue<'v> for Visitor<'f1, 'f2> {
+            fn visit_any(&mut self, value: Value) -> Result<(), kv::Error> { Ok(write_quoted(self.f, &value.to_string())?) }
+
+            fn visit_null(&mut self) -> Result<(), kv::Error> { Ok(self.f.write_str("null")?) }
+            fn visit_u64(&mut self, value: u64) -> Result<(), kv::Error> { Ok(write!(self.f, "{value}")?) }
+            fn visit_i64(&mut self, value: i64) -> Result<(), kv::Error> { Ok(write!(self.f, "{value}")?) }
+            fn visit_u128(&mut self, value: u128) -> Result<(), kv::Error> { Ok(write!(self.f, "{value}")?) }
+            fn visit_i128(&mut self, value: i128) -> Result<(), kv::Error> { Ok(write!(self.f, "{value}")?) }
+            fn visit_f64(&mut self, value: f64) -> Result<(), kv::Error> {
+                if self.json && !value.is_finite() { return Ok(self.f.write_str("null")?); }
+                Ok(write!(self.f, "{value}")?)
+            }
+            fn visit_bool(&mut self, value: bool) -> Result<(), kv::Error> { Ok(write!(self.f, "{value}")?) }
+            fn visit_str(&mut self, value: &str) -> Result<(), kv::Error> { Ok(write_quoted(self.f, value)?) }
+            fn visit_char(&mut self, value: char) -> Result<(), kv::Error> { Ok(write_quoted(self.f, value.encode_utf8(&mut [0; 4]))?) }
+            fn visit_error(&mut self, err: &(dyn Error + 'static)) -> Result<(), kv::Error> { Ok(write_quoted(self.f, &DisplayChain(err).to_string())?) }
+            fn visit_borrowed_error(&mut self, err: &'v (dyn Error + 'static)) -> Result<(), kv::Error> { self.visit_error(err) }
+        }
+
+        // Let the value decide which of the visitor's functions to call
+        self.value.visit(Visitor { f, json: self.json }).map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Formats an error followed by all of its sources, separated by colons (e.g., `Failed to open file: No such file or directory`).
+struct DisplayChain<'e>(&'e (dyn Error + 'static));
+impl<'e> Display for DisplayChain<'e> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        write!(f, "{}", self.0)?;
+        let mut source: Option<&(dyn Error + 'static)> = self.0.source();
+        while let Some(err) = source {
+            write!(f, ": {err}")?;
+            source = err.source();
+        }
+        Ok(())
+    }
+}
+
+/// Formats the key/value pairs attached to a [`Record`] in a way that fits the given [`DebugMode`].
+/// 
+/// In `DebugMode::Debug` and `DebugMode::Full`, this is a compact, dimmed `key=value` suffix. In `DebugMode::HumanFriendly`, it is an indented list with one pair per line.
+/// 
+/// Errors (i.e., values captured with `:err`) are written as their full chain of sources. In `DebugMode::HumanFriendly`, that's one indented `caused by:` line per error in the chain, which are written before any other pairs.
+struct DisplayKeyValues<'r, 'a, 't> {
+    /// The record who's key/value pairs we write.
+    record : &'r Record<'a>,
+    /// The theme that decides how errors are styled.
+    theme  : &'t Theme,
+    /// Whether to write with ANSI colours.
+    colour : bool,
+    /// The mode which determines the layout.
+    mode   : DebugMode,
+    /// The number of spaces by which the record is indented (only used in `DebugMode::HumanFriendly`).
+    indent : usize,
+}
+impl<'r, 'a, 't> Display for DisplayKeyValues<'r, 'a, 't> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        /// Visitor that writes every pair to a formatter.
+        struct Visitor<'f1, 'f2> {
+            f      : &'f1 mut FFormatter<'f2>,
+            theme  : &'f1 Theme,
+            colour : bool,
+            mode   : DebugMode,
+            indent : usize,
+            /// Whether this pass writes only the errors (`true`) or only the other pairs (`false`).
+            errors : bool,
+        }
+        impl<'f1, 'f2, 'kvs> VisitSource<'kvs> for Visitor<'f1, 'f2> {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                if self.mode == DebugMode::HumanFriendly {
+                    // Errors are written as a chain in a separate pass
+                    if let Some(err) = value.to_borrowed_error() {
+                        if self.errors { write_causes(self.f, err, self.theme, self.colour, self.indent)?; }
+                        return Ok(());
+                    }
+                    if self.errors { return Ok(()); }
+                    write!(self.f, "\n{:indent$}  - {}: {}", "", Style::new().force_styling(self.colour).bold().apply_to(key), DisplayValue { value: &value, json: false }, indent = self.indent)?;
+                } else {
+                    write!(self.f, " {}", Style::new().force_styling(self.colour).dim().apply_to(format_args!("{}={}", key, DisplayValue { value: &value, json: false })))?;
+                }
+                Ok(())
+            }
+        }
+
+        /// Writes an error and its sources as `caused by:` lines.
+        fn write_causes(f: &mut FFormatter<'_>, err: &(dyn Error + 'static), theme: &Theme, colour: bool, indent: usize) -> FResult {
+            let mut source: Option<&(dyn Error + 'static)> = Some(err);
+            while let Some(err) = source {
+                write!(f, "\n{:indent$}  {} {}", "", paint(theme.level_painter(Level::Error), colour, "caused by:"), err)?;
+                source = err.source();
+            }
+            Ok(())
+        }
+
+        // Visit all the pairs in the record, doing the errors first in HumanFriendly mode
+        if self.mode == DebugMode::HumanFriendly {
+            self.record.key_values().visit(&mut Visitor { f, theme: self.theme, colour: self.colour, mode: self.mode, indent: self.indent, errors: true }).map_err(|_| std::fmt::Error)?;
+        }
+        self.record.key_values().visit(&mut Visitor { f, theme: self.theme, colour: self.colour, mode: self.mode, indent: self.indent, errors: false }).map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Formats a [`Record`] as a single-line JSON object.
+/// 
+/// The object contains the `timestamp`, `level`, `target`, `module_path`, `file`, `line` and `message` of the record, as well as a nested `kv` object with its key/value pairs. Fields that the record does not have are written as `null`.
+struct DisplayJson<'r, 'a, 't> {
+    /// The record to write.
+    record    : &'r Record<'a>,
+    /// The style in which to write the timestamp.
+    timestamp : &'t TimestampStyle,
+    /// The time at which the record was logged.
+    time      : DateTime<Utc>,
+    /// Which thread and process fields to write.
+    fields    : PrefixFields,
+    /// The scopes the record was logged in.
+    scopes    : ScopeStack<'t>,
+}
+impl<'r, 'a, 't> Display for DisplayJson<'r, 'a, 't> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        /// Visitor that writes every pair as a JSON field.
+        struct Visitor<'f1, 'f2> {
+            f     : &'f1 mut FFormatter<'f2>,
+            first : bool,
+        }
+        impl<'f1, 'f2, 'kvs> VisitSource<'kvs> for Visitor<'f1, 'f2> {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                if !self.first { self.f.write_char(',')?; }
+                self.first = false;
+                write_quoted(self.f, key.as_str())?;
+                write!(self.f, ":{}", DisplayValue { value: &value, json: true })?;
+                Ok(())
+            }
+        }
+
+        // Write the fixed fields first
+        if self.timestamp.is_enabled() {
+            write!(f, "{{\"timestamp\":\"{}\"", self.timestamp.at(self.time, DebugMode::Full))?;
+        } else {
+            f.write_str("{\"timestamp\":null")?;
+        }
+        write!(f, ",\"level\":\"{}\",\"target\":", self.record.level())?;
+        write_quoted(f, self.record.target())?;
+        f.write_str(",\"module_path\":")?;
+        match self.record.module_path() {
+            Some(module_path) => write_quoted(f, module_path)?,
+            None              => f.write_str("null")?,
+        }
+        f.write_str(",\"file\":")?;
+        match self.record.file() {
+            Some(file) => write_quoted(f, file)?,
+            None       => f.write_str("null")?,
+        }
+        match self.record.line() {
+            Some(line) => write!(f, ",\"line\":{line}")?,
+            None       => f.write_str(",\"line\":null")?,
+        }
+
+        // Then the thread and process, if enabled
+        if self.fields.hostname() {
+            f.write_str(",\"hostname\":")?;
+            match fields::hostname() {
+                Some(hostname) => write_quoted(f, hostname)?,
+                None           => f.write_str("null")?,
+            }
+        }
+        if self.fields.pid() {
+            write!(f, ",\"pid\":{}", std::process::id())?;
+        }
+        if self.fields.thread() {
+            f.write_str(",\"thread\":")?;
+            write_quoted(f, &fields::thread())?;
+        }
+        if !self.scopes.is_empty() {
+            f.write_str(",\"scopes\":[")?;
+            let mut res: FResult = Ok(());
+            let mut first: bool = true;
+            self.scopes.for_each(|text| {
+                if res.is_ok() && !first { res = f.write_char(','); }
+                if res.is_ok() { res = write_quoted(f, text); }
+                first = false;
+            });
+            res?;
+            f.write_char(']')?;
+        }
+
+        // Then the message, which we escape as we go
+        f.write_str(",\"message\":\"")?;
+        write!(Escaper(f), "{}", self.record.args())?;
+        f.write_char('"')?;
+
+        // Finally, the key/value pairs
+        f.write_str(",\"kv\":{")?;
+        self.record.key_values().visit(&mut Visitor { f, first: true }).map_err(|_| std::fmt::Error)?;
+        f.write_str("}}")
+    }
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines how the continuation lines of messages that span multiple lines (e.g., a pretty-printed config or a backtrace) are laid out.
+/// 
+/// This only applies to the human-readable layouts, i.e., the [`HumanFriendlyFormatter`], [`DebugFormatter`] and [`FullFormatter`]. The width of the prefix is measured as it appears on screen, so ANSI colours don't affect the alignment.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugFormatter, DebugMode, FormatContext, Formatter as _, MultilineStyle, TimestampStyle};
+/// use log::{Level, Record};
+/// 
+/// let record: Record = Record::builder().level(Level::Error).target("debug").args(format_args!("Failed to parse config:\nexpected a number at line 4")).build();
+/// let timestamp: TimestampStyle = TimestampStyle::none();
+/// 
+/// let mut buf: Vec<u8> = Vec::new();
+/// DebugFormatter.format(&mut buf, &record, &FormatContext::new(true, DebugMode::Debug).with_timestamp(&timestamp).with_multiline(MultilineStyle::Indent)).unwrap();
+/// assert!(String::from_utf8(buf).unwrap().ends_with("\n              expected a number at line 4\n"));
+/// 
+/// // Wide characters take up two columns
+/// let record: Record = Record::builder().level(Level::Error).target("配置").args(format_args!("Failed to parse config:\nexpected a number at line 4")).build();
+/// let mut buf: Vec<u8> = Vec::new();
+/// DebugFormatter.format(&mut buf, &record, &FormatContext::new(true, DebugMode::Debug).with_timestamp(&timestamp).with_multiline(MultilineStyle::Indent)).unwrap();
+/// assert!(String::from_utf8(buf).unwrap().ends_with("\n             expected a number at line 4\n"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MultilineStyle {
+    /// Continuation lines are written as-is, starting at the first column.
+    /// 
+    /// This is the default.
+    #[default]
+    Verbatim,
+    /// Continuation lines are indented to align with the start of the message.
+    /// 
+    /// This looks like:
+    /// ```bash
+    /// [2023-03-03T18:10:13+01:00 ERROR debug] Failed to parse config:
+    ///                                         expected a number at line 4
+    /// ```
+    Indent,
+    /// Like [`MultilineStyle::Indent`], but with a gutter (`│`) just before the continuation lines.
+    /// 
+    /// This looks like:
+    /// ```bash
+    /// [2023-03-03T18:10:13+01:00 ERROR debug] Failed to parse config:
+    ///                                       │ expected a number at line 4
+    /// ```
+    Gutter,
+}
+
+/// Defines the widths to which the columns of the prefix written by the [`DebugFormatter`] and the [`FullFormatter`] are padded, so that messages start at the same column and are easy to scan.
+/// 
+/// Columns that are too wide are elided from the left (e.g., `…ver::http`), except for the level, which is only padded. Widths are measured in visible characters, so ANSI colours don't affect them. Columns without a width are written as-is.
+/// 
+/// In the [`DebugFormatter`], the target column also contains the module path (if it differs from the target).
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{Alignment, DebugFormatter, DebugMode, FormatContext, Formatter as _, TimestampStyle};
+/// use log::{Level, Record};
+/// 
+/// let timestamp: TimestampStyle = TimestampStyle::none();
+/// let ctx: FormatContext = FormatContext::new(true, DebugMode::Debug).with_timestamp(&timestamp).with_alignment(Alignment::new().with_level_width(7).with_target_width(10));
+/// 
+/// let mut buf: Vec<u8> = Vec::new();
+/// DebugFormatter.format(&mut buf, &Record::builder().level(Level::Info).target("app").args(format_args!("Hello there!")).build(), &ctx).unwrap();
+/// DebugFormatter.format(&mut buf, &Record::builder().level(Level::Warn).target("app::server::http").args(format_args!("General Kenobi!")).build(), &ctx).unwrap();
+/// assert_eq!(console::strip_ansi_codes(&String::from_utf8(buf).unwrap()), "[INFO    app       ] Hello there!\n[WARNING …ver::http] General Kenobi!\n");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Alignment {
+    /// The width of the level column, if any.
+    level    : Option<usize>,
+    /// The width of the target column, if any.
+    target   : Option<usize>,
+    /// The width of the `file:line` column, if any.
+    location : Option<usize>,
+}
+impl Alignment {
+    /// Constructor for the Alignment that doesn't align any column.
+    /// 
+    /// This is the default.
+    /// 
+    /// # Returns
+    /// A new Alignment that writes every column as-is.
+    #[inline]
+    pub const fn new() -> Self { Self { level: None, target: None, location: None } }
+
+    /// Constructor for the Alignment that aligns every column to a width that fits most records.
+    /// 
+    /// # Returns
+    /// A new Alignment with a level column of 7 characters (i.e., `WARNING`), a target column of 24 characters and a `file:line` column of 32 characters.
+    #[inline]
+    pub const fn columns() -> Self { Self { level: Some(7), target: Some(24), location: Some(32) } }
+
+
+
+    /// Changes the width of the level column.
+    /// 
+    /// # Arguments
+    /// - `width`: The width to pad the level to.
+    /// 
+    /// # Returns
+    /// The same Alignment as `self`, but with the new level width set.
+    #[inline]
+    pub const fn with_level_width(mut self, width: usize) -> Self {
+        self.level = Some(width);
+        self
+    }
+
+    /// Changes the width of the target column.
+    /// 
+    /// # Arguments
+    /// - `width`: The width to pad or elide the target to.
+    /// 
+    /// # Returns
+    /// The same Alignment as `self`, but with the new target width set.
+    #[inline]
+    pub const fn with_target_width(mut self, width: usize) -> Self {
+        self.target = Some(width);
+        self
+    }
+
+    /// Changes the width of the `file:line` column.
+    /// 
+    /// # Arguments
+    /// - `width`: The width to pad or elide the location to.
+    /// 
+    /// # Returns
+    /// The same Alignment as `self`, but with the new location width set.
+    #[inline]
+    pub const fn with_location_width(mut self, width: usize) -> Self {
+        self.location = Some(width);
+        self
+    }
+
+
+
+    /// Returns the width of the level column, if any.
+    #[inline]
+    pub const fn level_width(&self) -> Option<usize> { self.level }
+
+    /// Returns the width of the target column, if any.
+    #[inline]
+    pub const fn target_width(&self) -> Option<usize> { self.target }
+
+    /// Returns the width of the `file:line` column, if any.
+    #[inline]
+    pub const fn location_width(&self) -> Option<usize> { self.location }
+}
+
+
+
+/// Describes the context in which a [`Formatter`] is asked to format a record.
+/// 
+/// This is non-exhaustive, as future versions of this crate may pass additional information to formatters.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct FormatContext<'a> {
+    /// Whether the formatter should write ANSI colours or not.
+    pub colour    : bool,
+    /// The `DebugMode` the HumanLogger is currently in.
+    pub mode      : DebugMode,
+    /// The style in which timestamps should be written.
+    pub timestamp : &'a TimestampStyle,
+    /// How the continuation lines of multi-line messages should be laid out.
+    pub multiline : MultilineStyle,
+    /// The labels and colours with which records should be written.
+    pub theme     : &'a Theme,
+    /// The widths to which the columns of the prefix should be aligned.
+    pub alignment : Alignment,
+    /// Which thread and process fields should be written.
+    pub fields    : PrefixFields,
+    /// The scopes (see [`scope::enter()`](crate::scope::enter())) that were active when the record was logged.
+    pub scopes    : ScopeStack<'a>,
+    /// The time at which the record was logged, which is the same for every writer.
+    pub time      : DateTime<Utc>,
+}
+impl FormatContext<'static> {
+    /// Constructor for the FormatContext.
+    /// 
+    /// This is mostly useful to test custom [`Formatter`]s, as the HumanLogger will build these by itself.
+    /// 
+    /// # Arguments
+    /// - `colour`: Whether the formatter should write ANSI colours or not.
+    /// - `mode`: The `DebugMode` the HumanLogger is in.
+    /// 
+    /// # Returns
+    /// A new FormatContext with the given values, the default `TimestampStyle`, the default `MultilineStyle`, the default `Theme`, no `Alignment`, no `PrefixFields`, no scopes and the current time.
+    #[inline]
+    pub fn new(colour: bool, mode: DebugMode) -> Self {
+        /// The default timestamp style to refer to.
+        static DEFAULT_TIMESTAMP: TimestampStyle = TimestampStyle::local();
+        /// The default theme to refer to.
+        static DEFAULT_THEME: OnceLock<Theme> = OnceLock::new();
+        Self { colour, mode, timestamp: &DEFAULT_TIMESTAMP, multiline: MultilineStyle::Verbatim, theme: DEFAULT_THEME.get_or_init(Theme::new), alignment: Alignment::new(), fields: PrefixFields::none(), scopes: ScopeStack::empty(), time: Utc::now() }
+    }
+}
+impl<'a> FormatContext<'a> {
+    /// Changes the timestamp style in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The `TimestampStyle` that formatters should write timestamps in.
+    /// 
+    /// # Returns
+    /// A new FormatContext with the same values as `self`, except for the timestamp style.
+    #[inline]
+    pub fn with_timestamp<'b>(self, timestamp: &'b TimestampStyle) -> FormatContext<'b> where 'a: 'b {
+        FormatContext { colour: self.colour, mode: self.mode, timestamp, multiline: self.multiline, theme: self.theme, alignment: self.alignment, fields: self.fields, scopes: self.scopes, time: self.time }
+    }
+
+    /// Changes the theme in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `theme`: The `Theme` with which formatters should write records.
+    /// 
+    /// # Returns
+    /// A new FormatContext with the same values as `self`, except for the theme.
+    #[inline]
+    pub fn with_theme<'b>(self, theme: &'b Theme) -> FormatContext<'b> where 'a: 'b {
+        FormatContext { colour: self.colour, mode: self.mode, timestamp: self.timestamp, multiline: self.multiline, theme, alignment: self.alignment, fields: self.fields, scopes: self.scopes, time: self.time }
+    }
+
+    /// Changes how continuation lines are laid out in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `multiline`: The `MultilineStyle` that formatters should lay out continuation lines in.
+    /// 
+    /// # Returns
+    /// The same FormatContext as `self`, except for the multiline style.
+    #[inline]
+    pub fn with_multiline(mut self, multiline: MultilineStyle) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// Changes how the columns of the prefix are aligned in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `alignment`: The `Alignment` that formatters should align the columns of the prefix to.
+    /// 
+    /// # Returns
+    /// The same FormatContext as `self`, except for the alignment.
+    #[inline]
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Changes which thread and process fields are written in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `fields`: The `PrefixFields` that formatters should write.
+    /// 
+    /// # Returns
+    /// The same FormatContext as `self`, except for the fields.
+    #[inline]
+    pub fn with_fields(mut self, fields: PrefixFields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Changes the time at which the record was logged in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `time`: The time that formatters should write as the timestamp of the record.
+    /// 
+    /// # Returns
+    /// The same FormatContext as `self`, except for the time.
+    #[inline]
+    pub fn with_time(mut self, time: DateTime<Utc>) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Changes the scopes in this FormatContext.
+    /// 
+    /// # Arguments
+    /// - `scopes`: The `ScopeStack` with the scopes that the record was logged in.
+    /// 
+    /// # Returns
+    /// A new FormatContext with the same values as `self`, except for the scopes.
+    #[inline]
+    pub fn with_scopes<'b>(self, scopes: ScopeStack<'b>) -> FormatContext<'b> where 'a: 'b {
+        FormatContext { colour: self.colour, mode: self.mode, timestamp: self.timestamp, multiline: self.multiline, theme: self.theme, alignment: self.alignment, fields: self.fields, scopes, time: self.time }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines how a log record is written to a [`LogWriter`](crate::LogWriter).
+/// 
+/// The HumanLogger takes care of routing records to the correct writers, resolving the `ColourChoice` and handling write errors; formatters only decide on the layout.
+/// 
+/// # Examples
+/// ```rust
+/// use std::io::Write;
+/// use humanlog::{ColourChoice, DebugMode, FormatContext, Formatter, HumanLogger, LogWriter};
+/// use log::{Level, Record};
+/// 
+/// /// A formatter that writes the company-standard layout.
+/// struct CompanyFormatter;
+/// impl Formatter for CompanyFormatter {
+///     fn format(&self, writer: &mut dyn Write, record: &Record, _ctx: &FormatContext) -> std::io::Result<()> {
+///         writeln!(writer, "{} | {} | {}", record.level(), record.target(), record.args())
+///     }
+/// }
+/// 
+/// let logger: LogWriter = LogWriter::new(std::io::stdout(), ColourChoice::Auto, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "stdout").with_formatter(CompanyFormatter);
+/// if let Err(err) = HumanLogger::new(vec![ logger ], DebugMode::Debug).init() {
+///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+/// }
+/// ```
+pub trait Formatter: Send + Sync {
+    /// Writes the given record to the given writer.
+    /// 
+    /// Note that the HumanLogger has already decided the record should be written at this point; formatters should not filter records themselves.
+    /// 
+    /// # Arguments
+    /// - `writer`: The `Write`r to write the formatted record to. Implementations are expected to write the trailing newline themselves.
+    /// - `record`: The `Record` to format.
+    /// - `ctx`: A `FormatContext` that describes whether to use colours, which `DebugMode` is active and how to write timestamps.
+    /// 
+    /// # Errors
+    /// This function should error if writing to the `writer` failed.
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()>;
+
+    /// Returns a name that identifies the layout this formatter writes, if it only depends on the record and the [`FormatContext`].
+    /// 
+    /// The HumanLogger renders a record only once for all writers whose formatters return the same name (and that have the same colour, timestamp and field settings), and writes the result to each of them. Formatters with settings of their own should therefore return [`None`], which is the default, so that every writer renders the record itself.
+    /// 
+    /// # Returns
+    /// The name of the layout, e.g., `"debug"`, or [`None`] if records should be rendered separately for every writer.
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { None }
+}
+
+
+
+/// The default [`Formatter`], which writes records in the layout matching the active [`DebugMode`].
+/// 
+/// Concretely, it defers to the [`HumanFriendlyFormatter`], the [`DebugFormatter`] or the [`FullFormatter`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PrettyFormatter;
+impl Formatter for PrettyFormatter {
+    #[inline]
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> {
+        match ctx.mode {
+            DebugMode::HumanFriendly => HumanFriendlyFormatter.format(writer, record, ctx),
+            DebugMode::Debug         => DebugFormatter.format(writer, record, ctx),
+            DebugMode::Full          => FullFormatter.format(writer, record, ctx),
+        }
+    }
+
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { Some("pretty") }
+}
+
+/// A [`Formatter`] that always writes records in the layout of [`DebugMode::HumanFriendly`], regardless of the active mode.
+/// 
+/// This looks like:
+/// ```bash
+/// ERROR: This is an error!
+/// ```
+/// 
+/// Errors attached to the record as key/value pairs (captured with `:err`) are written as their full chain of causes, which is the easiest way to tell users _why_ something failed:
+/// ```rust
+/// use std::io::{Error, ErrorKind};
+/// use humanlog::DebugMode;
+/// use humanlog::capture::Capture;
+/// use log::error;
+/// 
+/// let capture: Capture = Capture::start().with_mode(DebugMode::HumanFriendly);
+/// let err: Error = Error::new(ErrorKind::NotFound, "No such file or directory");
+/// error!(err:err; "Failed to load config");
+/// assert_eq!(capture.text(), "ERROR: Failed to load config\n  caused by: No such file or directory\n");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HumanFriendlyFormatter;
+impl Formatter for HumanFriendlyFormatter {
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> {
+        // Introduce any new scopes, and indent the record under them
+        write_scope_headers(writer, ctx.scopes, ctx.theme, ctx.colour)?;
+        let indent: usize = 2 * ctx.scopes.depth();
+        let mut prefix: Measure = Measure::new(writer, ctx.multiline != MultilineStyle::Verbatim);
+        write!(prefix, "{:indent$}", "")?;
+        write_level(&mut prefix, record.level(), ctx.theme, None, ctx.colour)?;
+        prefix.write_all(b": ")?;
+        let width: usize = prefix.width();
+
+        // Write the message, aligning any continuation lines with its start
+        let message = DisplayMessage { args: record.args(), style: ctx.multiline, width, colour: ctx.colour };
+        writeln!(writer, "{}{}", message, DisplayKeyValues { record, theme: ctx.theme, colour: ctx.colour, mode: DebugMode::HumanFriendly, indent })
+    }
+
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { Some("human-friendly") }
+}
+
+/// A [`Formatter`] that always writes records in the layout of [`DebugMode::Debug`], regardless of the active mode.
+/// 
+/// This looks like:
+/// ```bash
+/// [2023-03-03T18:10:13+01:00 ERROR debug] This is an error!
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DebugFormatter;
+impl Formatter for DebugFormatter {
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> {
+        // Write the time
+        let mut prefix: Measure = Measure::new(writer, ctx.multiline != MultilineStyle::Verbatim);
+        prefix.write_all(b"[")?;
+        if ctx.timestamp.is_enabled() {
+            write!(prefix, "{} ", paint(ctx.theme.timestamp_painter(), ctx.colour, ctx.timestamp.at(ctx.time, DebugMode::Debug)))?;
+        }
+        write_fields(&mut prefix, ctx.fields, ctx.theme, ctx.colour)?;
+        // Write the verbosity level
+        write_level(&mut prefix, record.level(), ctx.theme, ctx.alignment.level, ctx.colour)?;
+        // Write the module and the target
+        prefix.write_all(b" ")?;
+        let target: &str = record.target();
+        let space: Painter = Painter::default();
+        match record.module_path() {
+            // We only add the module if it actually differs
+            Some(module_path) if module_path != target => {
+                write_column(&mut prefix, &[ (module_path, ctx.theme.module_painter()), (" ", &space), (target, ctx.theme.target_painter()) ], ctx.alignment.target, ctx.colour)?
+            },
+            _ => write_column(&mut prefix, &[ (target, ctx.theme.target_painter()) ], ctx.alignment.target, ctx.colour)?,
+        }
+        prefix.write_all(b"] ")?;
+        // Write the scopes the record was logged in, if any
+        write_scopes(&mut prefix, ctx.scopes, ctx.theme, ctx.colour)?;
+        let width: usize = prefix.width();
+
+        // Now write the message, followed by any key/value pairs
+        let message = DisplayMessage { args: record.args(), style: ctx.multiline, width, colour: ctx.colour };
+        writeln!(writer, "{}{}", message, DisplayKeyValues { record, theme: ctx.theme, colour: ctx.colour, mode: DebugMode::Debug, indent: 0 })
+    }
+
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { Some("debug") }
+}
+
+/// A [`Formatter`] that always writes records in the layout of [`DebugMode::Full`], regardless of the active mode.
+/// 
+/// This looks like:
+/// ```bash
+/// [2023-03-03T18:11:37.853292702+01:00 ERROR examples/full.rs:27 full] This is an error!
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FullFormatter;
+impl Formatter for FullFormatter {
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> {
+        // Write the time
+        let mut prefix: Measure = Measure::new(writer, ctx.multiline != MultilineStyle::Verbatim);
+        prefix.write_all(b"[")?;
+        if ctx.timestamp.is_enabled() {
+            write!(prefix, "{} ", paint(ctx.theme.timestamp_painter(), ctx.colour, ctx.timestamp.at(ctx.time, DebugMode::Full)))?;
+        }
+        write_fields(&mut prefix, ctx.fields, ctx.theme, ctx.colour)?;
+        // Write the verbosity level
+        write_level(&mut prefix, record.level(), ctx.theme, ctx.alignment.level, ctx.colour)?;
+        // Write the location (or only its padding, if it's missing but aligned)
+        if record.file().is_some() || ctx.alignment.location.is_some() {
+            prefix.write_all(b" ")?;
+            let mut line: [u8; 11] = [0; 11];
+            let line: &str = record.line().filter(|_| record.file().is_some()).map(|l| write_line(&mut line, l)).unwrap_or("");
+            write_column(&mut prefix, &[ (record.file().unwrap_or(""), ctx.theme.location_painter()), (line, ctx.theme.location_painter()) ], ctx.alignment.location, ctx.colour)?;
+        }
+        // Write the target
+        prefix.write_all(b" ")?;
+        write_column(&mut prefix, &[ (record.target(), ctx.theme.target_painter()) ], ctx.alignment.target, ctx.colour)?;
+        prefix.write_all(b"] ")?;
+        // Write the scopes the record was logged in, if any
+        write_scopes(&mut prefix, ctx.scopes, ctx.theme, ctx.colour)?;
+        let width: usize = prefix.width();
+
+        // Now write the message, followed by any key/value pairs
+        let message = DisplayMessage { args: record.args(), style: ctx.multiline, width, colour: ctx.colour };
+        writeln!(writer, "{}{}", message, DisplayKeyValues { record, theme: ctx.theme, colour: ctx.colour, mode: DebugMode::Full, indent: 0 })
+    }
+
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { Some("full") }
+}
+
+/// A [`Formatter`] that writes records as [JSON Lines](https://jsonlines.org), i.e., one JSON object per record. Colour is never applied.
+/// 
+/// Every object contains the fields `timestamp`, `level`, `target`, `module_path`, `file`, `line`, `message` and `kv` (the latter being an object with the record's key/value pairs). If enabled in the [`PrefixFields`], the `hostname`, `pid` and `thread` fields are added after `line`, followed by a `scopes` array if the record was logged in any [scope](crate::scope::enter()). The `DebugMode` does not influence the format, only which records are written.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{ColourChoice, JsonFormatter, LogWriter};
+/// use log::Level;
+/// 
+/// // Writes a line like:
+/// // {"timestamp":"2023-03-03T18:11:37.853292702+01:00","level":"INFO","target":"json","module_path":"json","file":"src/main.rs","line":4,"message":"Hello there!","kv":{"user_id":42}}
+/// let logger: LogWriter = LogWriter::new(std::io::stdout(), ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "stdout").with_formatter(JsonFormatter);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonFormatter;
+impl Formatter for JsonFormatter {
+    #[inline]
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> std::io::Result<()> {
+        writeln!(writer, "{}", DisplayJson { record, timestamp: ctx.timestamp, time: ctx.time, fields: ctx.fields, scopes: ctx.scopes })
+    }
+
+    #[inline]
+    fn layout(&self) -> Option<&'static str> { Some("json") }
+}
@@ -0,0 +1,140 @@
+//  GATE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:45:57
+//  Last edited:
+//    16 Oct 2026, 15:45:57
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the [`Gate`], a summary of the configuration of a
+//!   [`HumanLogger`](crate::HumanLogger) that answers `Log::enabled()`
+//!   without locking any writer.
+// 
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use log::{Level, LevelFilter};
+use parking_lot::{MutexGuard, RwLock};
+
+use crate::filter::Directives;
+use crate::recovery::WriterState;
+use crate::{Config, InternalLogWriter};
+
+
+/***** CONSTANTS *****/
+/// No writer will write the level, regardless of the target.
+const NEVER: u8 = 0;
+/// Some writer will write the level, regardless of the target.
+const ALWAYS: u8 = 1;
+/// Whether the level is written depends on the target.
+const TARGETED: u8 = 2;
+
+/// The levels in the order of their index in the [`Gate`].
+const LEVELS: [Level; 5] = [ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the index of the given level in the per-level arrays of a [`Gate`].
+#[inline]
+fn index(level: Level) -> usize {
+    match level {
+        Level::Error => 0,
+        Level::Warn  => 1,
+        Level::Info  => 2,
+        Level::Debug => 3,
+        Level::Trace => 4,
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A summary of the configuration of a [`HumanLogger`](crate::HumanLogger), which decides whether a record would be written by any writer.
+/// 
+/// For every level, it remembers whether it's never written, always written or written depending on the target. Only the latter requires looking at directives (which are merged over all writers beforehand), so checking a level that no writer writes is a single atomic load.
+/// 
+/// The Gate must be updated with [`Gate::update()`] whenever the writers, their state, the debug mode or any directives change.
+pub(crate) struct Gate {
+    /// The verdict for every level.
+    levels   : [AtomicU8; 5],
+    /// For levels that depend on the target, directives that tell if any writer writes the level for a target.
+    targeted : RwLock<[Directives; 5]>,
+}
+impl Default for Gate {
+    #[inline]
+    fn default() -> Self { Self { levels: Default::default(), targeted: Default::default() } }
+}
+impl Gate {
+    /// Recomputes the verdicts from the given configuration.
+    /// 
+    /// This locks every writer of the configuration, so should not be called while holding one.
+    /// 
+    /// # Arguments
+    /// - `config`: The `Config` to summarize.
+    pub(crate) fn update(&self, config: &Config) {
+        let mut verdicts: [u8; 5] = [NEVER; 5];
+        let mut targeted: [Directives; 5] = Default::default();
+        for level in LEVELS {
+            let filter: LevelFilter = level.to_level_filter();
+
+            // Collect how the alive writers for this level decide, and which targets matter to any of them
+            let writers: Vec<MutexGuard<(WriterState, InternalLogWriter)>> = config.writers(level).iter().map(|w| w.lock()).filter(|lock| lock.0.is_alive()).collect();
+            let mut targets: Vec<&str> = config.directives.targets().collect();
+            for lock in &writers { targets.extend(lock.1.directives.targets()); }
+
+            // Decide for targets that aren't matched by any directive first
+            let writes = |target: Option<&str>| writers.iter().any(|lock| {
+                let max: Option<LevelFilter> = match target {
+                    Some(target) => lock.1.directives.level_for(target).or_else(|| config.directives.level_for(target)),
+                    None         => lock.1.directives.default_level().or(config.directives.default_level()),
+                };
+                level <= max.unwrap_or_else(|| config.debug.level_filter())
+            });
+            let default: bool = writes(None);
+
+            // Then see if any of the targets decides differently. Because directives match on path boundaries, the longest matching target of all writers decides for all of them.
+            let mut directives: Directives = Directives::new().with_default(if default { filter } else { LevelFilter::Off });
+            let mut uniform: bool = true;
+            for target in targets {
+                let verdict: bool = writes(Some(target));
+                uniform &= verdict == default;
+                directives = directives.with_target(target, if verdict { filter } else { LevelFilter::Off });
+            }
+
+            // The directives are correct for any verdict, so readers never see a wrong answer while we update
+            verdicts[index(level)] = if !uniform { TARGETED } else if default { ALWAYS } else { NEVER };
+            targeted[index(level)] = directives;
+        }
+
+        // Store the directives before the verdicts that refer to them
+        *self.targeted.write() = targeted;
+        for (verdict, store) in verdicts.into_iter().zip(&self.levels) {
+            store.store(verdict, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns whether any writer would write a record with the given level and target.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` of the record.
+    /// - `target`: The target of the record.
+    /// 
+    /// # Returns
+    /// True if the record should be passed to `Log::log()`, or false otherwise.
+    #[inline]
+    pub(crate) fn enabled(&self, level: Level, target: &str) -> bool {
+        match self.levels[index(level)].load(Ordering::Relaxed) {
+            NEVER  => false,
+            ALWAYS => true,
+            _      => self.targeted.read()[index(level)].level_for(target).is_some_and(|max| level <= max),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//  HANDLE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:17:29
+//  Last edited:
+//    16 Oct 2026, 15:57:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the [`LoggerHandle`], which can be used to change an
+//!   installed [`HumanLogger`](crate::HumanLogger) at runtime.
+// 
+
+use std::io::Write as _;
+use std::sync::Arc;
+
+use log::LevelFilter;
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::background::{Background, Sink};
+use crate::recovery::WriterState;
+use crate::{Alignment, ColourChoice, Config, DebugMode, Directives, InternalLogWriter, LevelSet, LogWriter, MultilineStyle, PrefixFields, SharedWriter, Theme, TimestampStyle, WriterStatus};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds all writers with the given label.
+/// 
+/// # Arguments
+/// - `config`: The `Config` to search in.
+/// - `label`: The label to search for.
+/// 
+/// # Returns
+/// All writers with exactly that label.
+fn find(config: &Config, label: &str) -> Vec<SharedWriter> {
+    config.unique_writers().into_iter().filter(|w| w.lock().1.label == label).cloned().collect()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A handle to an installed [`HumanLogger`](crate::HumanLogger) that can be used to change it at runtime.
+/// 
+/// Obtain one with [`HumanLogger::init_with_handle()`](crate::HumanLogger::init_with_handle()). It can be cloned and sent to other threads freely. Every change also updates `log`'s maximum level, so that newly enabled levels are actually passed to the logger.
+/// 
+/// Writers are identified by the label given to them when they were created (e.g., `stdout` and `stderr` for [`LogWriter::stdout()`] and [`LogWriter::stderr()`]). If multiple writers share a label, changes apply to all of them.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger, LoggerHandle};
+/// use log::{debug, Level, LevelFilter};
+/// 
+/// let handle: LoggerHandle = match HumanLogger::terminal(DebugMode::HumanFriendly).init_with_handle() {
+///     Ok(handle) => handle,
+///     Err(err)   => {
+///         eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+///         return;
+///     },
+/// };
+/// 
+/// // Not shown...
+/// debug!("Hello there!");
+/// 
+/// // ...until the mode is changed (e.g., because the daemon received `SIGUSR1`)
+/// assert_eq!(log::max_level(), LevelFilter::Warn);
+/// handle.set_mode(DebugMode::Full);
+/// assert_eq!(log::max_level(), LevelFilter::Trace);
+/// debug!("General Kenobi!");
+/// 
+/// // Writers can be reconfigured too
+/// assert!(handle.set_filter("stderr", vec![ Level::Error ]));
+/// assert!(!handle.set_filter("file", vec![ Level::Error ]));
+/// ```
+#[derive(Clone)]
+pub struct LoggerHandle {
+    /// The writers and settings of the installed logger.
+    pub(crate) config     : Arc<RwLock<Config>>,
+    /// The writer thread of the installed logger, if it writes asynchronously.
+    pub(crate) background : Option<Arc<Background>>,
+}
+impl LoggerHandle {
+    /// Returns the `DebugMode` that the logger currently logs with.
+    #[inline]
+    pub fn mode(&self) -> DebugMode { self.config.read().debug }
+
+    /// Changes the `DebugMode` of the logger.
+    /// 
+    /// This changes both which levels are logged (unless overridden by directives) and how records are formatted.
+    /// 
+    /// # Arguments
+    /// - `mode`: The new `DebugMode` to log with.
+    pub fn set_mode(&self, mode: DebugMode) {
+        self.config.write().debug = mode;
+        self.sync_max_level();
+    }
+
+    /// Replaces the `RUST_LOG`-style directives of the logger.
+    /// 
+    /// # Arguments
+    /// - `directives`: The new `Directives` to apply. See `HumanLogger::with_directives()` for more information.
+    pub fn set_directives(&self, directives: Directives) {
+        self.config.write().directives = directives;
+        self.sync_max_level();
+    }
+
+    /// Changes the style in which timestamps are written.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The new `TimestampStyle` to write timestamps in.
+    #[inline]
+    pub fn set_timestamp(&self, timestamp: TimestampStyle) { self.config.write().timestamp = timestamp; }
+
+    /// Changes how the continuation lines of multi-line messages are laid out.
+    /// 
+    /// # Arguments
+    /// - `multiline`: The new `MultilineStyle` to lay out continuation lines in.
+    #[inline]
+    pub fn set_multiline(&self, multiline: MultilineStyle) { self.config.write().multiline = multiline; }
+
+    /// Changes the labels and colours with which records are written.
+    /// 
+    /// # Arguments
+    /// - `theme`: The new `Theme` to write records with.
+    #[inline]
+    pub fn set_theme(&self, theme: Theme) { self.config.write().theme = theme; }
+
+    /// Changes how the columns of the prefix of records are aligned.
+    /// 
+    /// # Arguments
+    /// - `alignment`: The new `Alignment` that decides the widths of the columns.
+    #[inline]
+    pub fn set_alignment(&self, alignment: Alignment) { self.config.write().alignment = alignment; }
+
+    /// Changes which thread and process fields are written, for all writers that don't override them.
+    /// 
+    /// # Arguments
+    /// - `fields`: The new `PrefixFields` to write.
+    #[inline]
+    pub fn set_fields(&self, fields: PrefixFields) { self.config.write().fields = fields; }
+
+
+
+    /// Adds a new writer to the logger.
+    /// 
+    /// # Arguments
+    /// - `writer`: The `LogWriter` to add.
+    pub fn add_writer(&self, writer: LogWriter) {
+        let filter: LevelSet = writer.filter;
+        let mut writer: InternalLogWriter = writer.into();
+
+        // Let the writer thread do the actual writing if there is one
+        if let Some(background) = &self.background {
+            if let Sink::Direct(direct) = std::mem::replace(&mut writer.writer, Sink::Direct(Box::new(std::io::sink()))) {
+                writer.writer = Sink::Queued(background.add(direct));
+            }
+        }
+
+        self.config.write().add(Arc::new(Mutex::new((WriterState::Enabled, writer))), filter);
+        self.sync_max_level();
+    }
+
+    /// Removes writers from the logger.
+    /// 
+    /// The writers are flushed and then dropped (e.g., closing files).
+    /// 
+    /// # Arguments
+    /// - `label`: The label of the writer(s) to remove.
+    /// 
+    /// # Returns
+    /// Whether any writer with that label was found.
+    pub fn remove_writer(&self, label: &str) -> bool {
+        let mut config: RwLockWriteGuard<Config> = self.config.write();
+        let writers: Vec<SharedWriter> = find(&config, label);
+        for w in &writers {
+            config.remove(w);
+
+            // Drop the actual writer, which may live on the writer thread
+            let mut lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+            let _ = lock.1.writer.flush();
+            lock.1.writer.replace(Box::new(std::io::sink()));
+        }
+        drop(config);
+        self.sync_max_level();
+        !writers.is_empty()
+    }
+
+    /// Changes which levels are written to the given writers.
+    /// 
+    /// # Arguments
+    /// - `label`: The label of the writer(s) to change.
+    /// - `filter`: The new levels that are only allowed to be written to the writer(s), e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels (see [`LevelSet`]).
+    /// 
+    /// # Returns
+    /// Whether any writer with that label was found.
+    pub fn set_filter(&self, label: &str, filter: impl Into<LevelSet>) -> bool {
+        let filter: LevelSet = filter.into();
+        let mut config: RwLockWriteGuard<Config> = self.config.write();
+        let writers: Vec<SharedWriter> = find(&config, label);
+        for w in &writers {
+            config.remove(w);
+            config.add(w.clone(), filter);
+        }
+        drop(config);
+        self.sync_max_level();
+        !writers.is_empty()
+    }
+
+    /// Changes whether the given writers write ANSI colours.
+    /// 
+    /// # Arguments
+    /// - `label`: The label of the writer(s) to change.
+    /// - `colour`: The new `ColourChoice` for the writer(s). `ColourChoice::Auto` is resolved again based on what the writer writes to and the current environment.
+    /// 
+    /// # Returns
+    /// Whether any writer with that label was found.
+    pub fn set_colour(&self, label: &str, colour: ColourChoice) -> bool {
+        let writers: Vec<SharedWriter> = find(&self.config.read(), label);
+        for w in &writers {
+            let mut lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+            lock.1.colour = colour.resolve(lock.1.is_terminal);
+        }
+        !writers.is_empty()
+    }
+
+
+
+    /// Returns the writers that are currently not written to because writing to them failed.
+    /// 
+    /// # Returns
+    /// A [`WriterStatus`] for every writer that is disabled or waiting to be retried.
+    #[inline]
+    pub fn disabled_writers(&self) -> Vec<WriterStatus> { self.config.read().disabled_writers() }
+
+    /// Updates which records are enabled after the writers, debug mode or directives changed, including `log`'s maximum level, which is set to the most verbose level any writer (or active [`Capture`](crate::capture::Capture)) can currently write.
+    pub(crate) fn sync_max_level(&self) {
+        let config: RwLockReadGuard<Config> = self.config.read();
+        config.update_gate();
+        let max_level: LevelFilter = config.max_level();
+        log::set_max_level(max_level.max(crate::capture::active_level()));
+    }
+}
@@ -0,0 +1,285 @@
+//  JOURNALD.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:23:58
+//  Last edited:
+//    16 Oct 2026, 15:36:43
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements writing to the systemd journal using its native
+//!   protocol, with a [`JournaldFormatter`] that serializes records as
+//!   journal fields and a [`JournaldWriter`] that sends them.
+//! 
+//!   Only available on Linux.
+// 
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::mem;
+use std::os::unix::io::{AsRawFd as _, FromRawFd as _, RawFd};
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+
+use log::kv::{self, Key, Value, VisitSource};
+use log::Record;
+
+use crate::fields;
+use crate::formatter::{FormatContext, Formatter};
+use crate::syslog::{app_name, severity};
+
+
+/***** CONSTANTS *****/
+/// The path of the socket on which journald listens for native protocol messages.
+pub const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Writes a single field in the native journal protocol.
+/// 
+/// Values without newlines are written as `KEY=value\n`. Other values are written as the key, a newline, the length of the value as a little-endian 64-bit integer, the value itself and a final newline.
+/// 
+/// # Arguments
+/// - `writer`: The `Write`r to write to.
+/// - `key`: The (already valid) name of the field.
+/// - `value`: The value of the field.
+/// 
+/// # Errors
+/// This function errors if we failed to write to the given writer.
+fn write_field(writer: &mut dyn Write, key: &str, value: &[u8]) -> io::Result<()> {
+    writer.write_all(key.as_bytes())?;
+    if value.contains(&b'\n') {
+        writer.write_all(b"\n")?;
+        writer.write_all(&(value.len() as u64).to_le_bytes())?;
+    } else {
+        writer.write_all(b"=")?;
+    }
+    writer.write_all(value)?;
+    writer.write_all(b"\n")
+}
+
+/// Turns a key of a key/value pair into a valid journal field name.
+/// 
+/// Field names may only consist of uppercase letters, digits and underscores, may not start with a digit or an underscore (which are reserved for fields added by journald itself) and are at most 64 characters long.
+/// 
+/// # Arguments
+/// - `key`: The key to convert.
+/// 
+/// # Returns
+/// The key in uppercase, with any other characters replaced by underscores and any leading underscores removed. Keys that are empty or start with a digit afterwards are prefixed with `KV_`.
+fn field_name(key: &str) -> String {
+    let name: String = key.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' }).collect();
+    let name: &str = name.trim_start_matches('_');
+    let mut name: String = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) { format!("KV_{name}") } else { name.into() };
+    name.truncate(64);
+    name
+}
+
+/// Sends the given file descriptor over the given socket, without any payload.
+/// 
+/// # Arguments
+/// - `socket`: The (connected) socket to send on.
+/// - `fd`: The file descriptor to send.
+/// 
+/// # Errors
+/// This function errors if we failed to send the message.
+fn send_fd(socket: &UnixDatagram, fd: RawFd) -> io::Result<()> {
+    // Prepare a buffer for the control message, aligned for the header
+    // SAFETY: These only compute sizes
+    let space: usize = unsafe { libc::CMSG_SPACE(mem::size_of::<RawFd>() as u32) } as usize;
+    let len: usize = unsafe { libc::CMSG_LEN(mem::size_of::<RawFd>() as u32) } as usize;
+    let mut control: Vec<u64> = vec![0; space.div_ceil(mem::size_of::<u64>())];
+
+    // SAFETY: The message is zero-initialized (which is valid for all its fields), and the control buffer is large enough and aligned for the single header we write to it
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = space as _;
+        let cmsg: *mut libc::cmsghdr = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = len as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut RawFd, fd);
+
+        if libc::sendmsg(socket.as_raw_fd(), &msg, libc::MSG_NOSIGNAL) < 0 { return Err(io::Error::last_os_error()); }
+    }
+    Ok(())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A [`Formatter`] that serializes records as fields of the native systemd journal protocol.
+/// 
+/// Every record is written with the fields `PRIORITY` (mapped like syslog severities), `MESSAGE`, `SYSLOG_IDENTIFIER`, `TARGET` and, if the record has them, `CODE_FILE`, `CODE_LINE` and `CODE_MODULE`. If the thread is enabled in the [`PrefixFields`](crate::PrefixFields), `TID` and `THREAD_NAME` are written too. Every key/value pair is written as a field of its own, with its key converted to a valid field name (e.g., `user_id` becomes `USER_ID`). Colours and timestamps are never written, as journald adds its own.
+/// 
+/// The output is binary-safe but not meant for humans; use it with a [`JournaldWriter`], e.g., through [`LogWriter::journald()`](crate::LogWriter::journald()).
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, FormatContext, Formatter as _};
+/// use humanlog::journald::JournaldFormatter;
+/// use log::{Level, Record};
+/// 
+/// let record: Record = Record::builder().level(Level::Warn).target("kenobi").key_values(&[("user_id", 42)]).args(format_args!("Hello\nthere!")).build();
+/// 
+/// let mut buf: Vec<u8> = Vec::new();
+/// JournaldFormatter::new().with_identifier("example").format(&mut buf, &record, &FormatContext::new(false, DebugMode::Debug)).unwrap();
+/// assert_eq!(buf, b"PRIORITY=4\nMESSAGE\n\x0c\0\0\0\0\0\0\0Hello\nthere!\nSYSLOG_IDENTIFIER=example\nTARGET=kenobi\nUSER_ID=42\n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct JournaldFormatter {
+    /// The identifier to log as.
+    identifier : Option<String>,
+}
+impl Default for JournaldFormatter {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl JournaldFormatter {
+    /// Constructor for the JournaldFormatter.
+    /// 
+    /// # Returns
+    /// A new JournaldFormatter that uses the name of the executable as `SYSLOG_IDENTIFIER`.
+    #[inline]
+    pub fn new() -> Self { Self { identifier: app_name() } }
+
+    /// Changes the identifier that records are logged as.
+    /// 
+    /// # Arguments
+    /// - `identifier`: The new `SYSLOG_IDENTIFIER` to log as.
+    /// 
+    /// # Returns
+    /// The same JournaldFormatter as `self`, but with the new identifier set.
+    #[inline]
+    pub fn with_identifier(mut self, identifier: impl Into<String>) -> Self { self.identifier = Some(identifier.into()); self }
+}
+impl Formatter for JournaldFormatter {
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> io::Result<()> {
+        // Write the fixed fields first
+        write_field(writer, "PRIORITY", severity(record.level()).to_string().as_bytes())?;
+        write_field(writer, "MESSAGE", record.args().to_string().as_bytes())?;
+        if let Some(identifier) = &self.identifier {
+            write_field(writer, "SYSLOG_IDENTIFIER", identifier.as_bytes())?;
+        }
+        write_field(writer, "TARGET", record.target().as_bytes())?;
+        if let Some(file) = record.file() {
+            write_field(writer, "CODE_FILE", file.as_bytes())?;
+        }
+        if let Some(line) = record.line() {
+            write_field(writer, "CODE_LINE", line.to_string().as_bytes())?;
+        }
+        if let Some(module_path) = record.module_path() {
+            write_field(writer, "CODE_MODULE", module_path.as_bytes())?;
+        }
+        // journald records the process ID and hostname by itself, but not the thread
+        if ctx.fields.thread() {
+            // SAFETY: `gettid()` cannot fail and has no preconditions
+            write_field(writer, "TID", unsafe { libc::gettid() }.to_string().as_bytes())?;
+            write_field(writer, "THREAD_NAME", fields::thread().as_bytes())?;
+        }
+
+        /// Visitor that writes every pair as a field.
+        struct Visitor<'w> {
+            writer : &'w mut dyn Write,
+        }
+        impl<'w, 'kvs> VisitSource<'kvs> for Visitor<'w> {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                Ok(write_field(self.writer, &field_name(key.as_str()), value.to_string().as_bytes())?)
+            }
+        }
+        record.key_values().visit(&mut Visitor { writer }).map_err(|err| io::Error::other(err.to_string()))
+    }
+}
+
+
+
+/// A writer that sends every write as one message to journald.
+/// 
+/// Messages that are too large for a single datagram are written to a sealed memory file instead, which is then passed to journald, just like `libsystemd` does. Use it with a [`JournaldFormatter`] to send valid messages, e.g., through [`LogWriter::journald()`](crate::LogWriter::journald()).
+/// 
+/// # Examples
+/// ```rust
+/// use std::os::unix::net::UnixDatagram;
+/// use humanlog::{DebugMode, HumanLogger, LogWriter};
+/// use humanlog::journald::JournaldFormatter;
+/// use log::{Level, Log as _, Record};
+/// 
+/// // Pretend to be journald
+/// let path = std::env::temp_dir().join(format!("humanlog-journald-{}.sock", std::process::id()));
+/// let _ = std::fs::remove_file(&path);
+/// let journald: UnixDatagram = UnixDatagram::bind(&path).unwrap();
+/// 
+/// // Use the logger directly instead of installing it, so it is private to this test
+/// let writer: LogWriter = LogWriter::journald(&path, JournaldFormatter::new().with_identifier("example"), vec![ Level::Error, Level::Warn, Level::Info ], "journald").unwrap();
+/// let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::Debug);
+/// logger.log(&Record::builder().level(Level::Info).target("kenobi").line(Some(42)).args(format_args!("Hello there!")).build());
+/// 
+/// let mut buf: [u8; 1024] = [0; 1024];
+/// let n: usize = journald.recv(&mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"PRIORITY=6\nMESSAGE=Hello there!\nSYSLOG_IDENTIFIER=example\nTARGET=kenobi\nCODE_LINE=42\n");
+/// std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct JournaldWriter {
+    /// The socket connected to journald.
+    socket : UnixDatagram,
+}
+impl JournaldWriter {
+    /// Constructor for the JournaldWriter that connects to journald.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the socket to connect to. This is typically [`JOURNALD_SOCKET`].
+    /// 
+    /// # Returns
+    /// A new JournaldWriter that is connected to journald.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to connect to the socket (e.g., because journald isn't running).
+    pub fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let socket: UnixDatagram = UnixDatagram::unbound()?;
+        socket.connect(path)?;
+        Ok(Self { socket })
+    }
+
+    /// Sends a message through a sealed memory file, for messages that are too large to send directly.
+    /// 
+    /// # Arguments
+    /// - `message`: The serialized message to send.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to create, write or seal the memory file, or to send it.
+    fn send_memfd(&self, message: &[u8]) -> io::Result<()> {
+        // SAFETY: The name is a valid C string
+        let fd: RawFd = unsafe { libc::memfd_create(c"humanlog-journald".as_ptr(), libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING) };
+        if fd < 0 { return Err(io::Error::last_os_error()); }
+        // SAFETY: We just created the descriptor, so nobody else owns it
+        let mut file: File = unsafe { File::from_raw_fd(fd) };
+        file.write_all(message)?;
+
+        // journald only accepts the file if it can't be changed anymore
+        // SAFETY: The descriptor is valid for as long as `file` lives
+        if unsafe { libc::fcntl(fd, libc::F_ADD_SEALS, libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE | libc::F_SEAL_SEAL) } < 0 { return Err(io::Error::last_os_error()); }
+        send_fd(&self.socket, fd)
+    }
+}
+impl Write for JournaldWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.socket.send(buf) {
+            Ok(_) => {},
+            Err(err) if matches!(err.raw_os_error(), Some(libc::EMSGSIZE) | Some(libc::ENOBUFS)) => self.send_memfd(buf)?,
+            Err(err) => return Err(err),
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    12 Feb 2023, 13:39:26
 //  Last edited:
-//    17 Mar 2023, 16:18:39
+//    16 Oct 2026, 15:57:16
 //  Auto updated?
 //    Yes
 // 
@@ -15,59 +15,134 @@
 //!   _debug_ mode.
 // 
 
+// Declare modules
+mod background;
+pub mod builder;
+pub mod capture;
+mod env;
+mod gate;
+pub mod fields;
+pub mod filter;
+pub mod formatter;
+pub mod handle;
+#[cfg(target_os = "linux")]
+pub mod journald;
+pub mod recovery;
+mod render;
+pub mod rotate;
+pub mod scope;
+pub mod syslog;
+pub mod theme;
+pub mod timestamp;
+
+// Imports
 use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter as FFormatter, Result as FResult};
 use std::io::{IsTerminal, Stderr, Stdin, Stdout, Write};
 use std::ops::DerefMut as _;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 
-use chrono::Local;
-use console::{style, Style};
-use log::{Level, LevelFilter, Log, SetLoggerError};
-use parking_lot::{Mutex, MutexGuard};
+use background::{Background, Sink};
+use chrono::{DateTime, Utc};
+use gate::Gate;
+use console::style;
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use recovery::WriterState;
+use render::{RenderKey, Renders};
 
+// Re-exports
+pub use background::OverflowPolicy;
+pub use builder::{HumanLoggerBuilder, LogWriterBuilder};
+pub use env::EnvConfig;
+pub use fields::PrefixFields;
+pub use filter::{DirectiveParseError, Directives, LevelSet};
+pub use formatter::{Alignment, DebugFormatter, FormatContext, Formatter, FullFormatter, HumanFriendlyFormatter, JsonFormatter, MultilineStyle, PrettyFormatter};
+pub use handle::LoggerHandle;
+#[cfg(target_os = "linux")]
+pub use journald::{JournaldFormatter, JournaldWriter};
+pub use recovery::{ErrorAction, ErrorPolicy, WriterStatus};
+pub use rotate::{RotatingFile, RotationInterval, RotationNaming, RotationPolicy};
+pub use syslog::{SyslogFacility, SyslogFormat, SyslogFormatter, SyslogTransport, SyslogWriter};
+pub use theme::Theme;
+pub use timestamp::{TimestampFormat, TimestampPrecision, TimestampStyle, TimestampStyleParseError, TimestampZone};
 
-/***** HELPER MACROS *****/
-/// Writes something to the given LogWriter.
-macro_rules! log_write {
-    ($enabled:ident, $writer:ident, $($t:tt)*) => {
-        if let Err(err) = write!($writer.writer, $($t)*) {
-            eprintln!("{}: Failed to write to writer '{}': {} (will not attempt again)", style("WARNING").yellow().bold(), $writer.label, err);
-            *$enabled = false;
-            continue;
-        }
-    };
-}
 
-/// Writes something to the given LogWriter but with a newline
-macro_rules! log_writeln {
-    ($enabled:ident, $writer:ident, $($t:tt)*) => {
-        if let Err(err) = writeln!($writer.writer, $($t)*) {
-            eprintln!("{}: Failed to write to writer '{}': {} (will not attempt again)", style("WARNING").yellow().bold(), $writer.label, err);
-            *$enabled = false;
-            continue;
-        }
-    };
+/***** HELPER FUNCTIONS *****/
+/// Finds out whether the given writer is a TTY.
+/// 
+/// # Arguments
+/// - `writer`: The writer to check.
+/// 
+/// # Returns
+/// True if the writer is stdin, stdout or stderr and that is a TTY, or else false.
+fn is_terminal(writer: &(impl 'static + Write)) -> bool {
+    if (writer as &dyn Any).downcast_ref::<Stdin>().is_some() {
+        std::io::stdin().is_terminal()
+    } else if (writer as &dyn Any).downcast_ref::<Stderr>().is_some() {
+        std::io::stderr().is_terminal()
+    } else if (writer as &dyn Any).downcast_ref::<Stdout>().is_some() {
+        std::io::stdout().is_terminal()
+    } else {
+        false
+    }
 }
 
+
+
+
+
+/***** HELPER MACROS *****/
 /// Flushes a given list of log writers.
 macro_rules! log_flush {
-    ($writers:expr) => {
+    ($writers:expr) => {{
+        let mut died: bool = false;
         for w in $writers {
-            let mut lock: MutexGuard<(bool, InternalLogWriter)> = w.lock();
-            let (enabled, writer): &mut (bool, InternalLogWriter) = lock.deref_mut();
-
-            // Skip if not enabled
-            if !*enabled { continue; }
+            let mut lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+            let (state, writer): &mut (WriterState, InternalLogWriter) = lock.deref_mut();
 
-            // Flush the writer (or at least, try to)
-            if let Err(err) = writer.writer.flush() {
-                eprintln!("{}: Failed to flush writer '{}': {} (will not attempt again)", style("WARNING").yellow().bold(), writer.label, err);
-                *enabled = false;
-                continue;
-            }
+            // Flush the writer (or at least, try to), remembering if that killed it
+            writer.policy.attempt(state, &writer.label, "flush", &mut writer.writer, |w| w.flush());
+            died |= !state.is_alive();
         }
+        died
+    }}
+}
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when parsing a [`DebugMode`] from a string.
+#[derive(Debug)]
+pub struct DebugModeParseError {
+    /// The raw value that we failed to parse.
+    raw : String,
+}
+impl Display for DebugModeParseError {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        write!(f, "Unknown debug mode '{}' (expected one of 'friendly', 'debug', 'full', '0', '1' or '2')", self.raw)
+    }
+}
+impl Error for DebugModeParseError {}
+
+/// Defines errors that occur when parsing a [`ColourChoice`] from a string.
+#[derive(Debug)]
+pub struct ColourChoiceParseError {
+    /// The raw value that we failed to parse.
+    raw : String,
+}
+impl Display for ColourChoiceParseError {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        write!(f, "Unknown colour choice '{}' (expected one of 'auto', 'always' or 'never')", self.raw)
     }
 }
+impl Error for ColourChoiceParseError {}
 
 
 
@@ -125,10 +200,10 @@ pub enum DebugMode {
     /// 
     /// This will show:
     /// ```bash
-    /// [2023-03-03T18:10:13Z ERROR debug] This is an error!
-    /// [2023-03-03T18:10:13Z WARNING debug] This is a warning!
-    /// [2023-03-03T18:10:13Z INFO debug] This is an info message!
-    /// [2023-03-03T18:10:13Z DEBUG debug] This is a debug message!
+    /// [2023-03-03T18:10:13+01:00 ERROR debug] This is an error!
+    /// [2023-03-03T18:10:13+01:00 WARNING debug] This is a warning!
+    /// [2023-03-03T18:10:13+01:00 INFO debug] This is an info message!
+    /// [2023-03-03T18:10:13+01:00 DEBUG debug] This is a debug message!
     /// ```
     Debug,
     /// Debugs everything, which is everything `DebugLevel::Debug` does, plus `Level::Trace`.
@@ -253,6 +328,61 @@ impl DebugMode {
             _ => DebugMode::Full,
         }
     }
+
+
+
+    /// Returns the most verbose level that is logged in this mode by default.
+    /// 
+    /// # Returns
+    /// `LevelFilter::Warn` for `DebugMode::HumanFriendly`, `LevelFilter::Debug` for `DebugMode::Debug` and `LevelFilter::Trace` for `DebugMode::Full`.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::DebugMode;
+    /// use log::LevelFilter;
+    /// 
+    /// assert_eq!(DebugMode::HumanFriendly.level_filter(), LevelFilter::Warn);
+    /// assert_eq!(DebugMode::Debug.level_filter(), LevelFilter::Debug);
+    /// assert_eq!(DebugMode::Full.level_filter(), LevelFilter::Trace);
+    /// ```
+    #[inline]
+    pub fn level_filter(&self) -> LevelFilter {
+        match self {
+            DebugMode::HumanFriendly => LevelFilter::Warn,
+            DebugMode::Debug         => LevelFilter::Debug,
+            DebugMode::Full          => LevelFilter::Trace,
+        }
+    }
+}
+
+impl FromStr for DebugMode {
+    type Err = DebugModeParseError;
+
+    /// Parses a DebugMode from a string.
+    /// 
+    /// Accepts the (case-insensitive) names `friendly`, `humanfriendly`, `debug` and `full` (or `trace`), as well as the numbers accepted by `DebugMode::from_num()`.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::DebugMode;
+    /// 
+    /// assert_eq!("friendly".parse::<DebugMode>().unwrap(), DebugMode::HumanFriendly);
+    /// assert_eq!("Debug".parse::<DebugMode>().unwrap(), DebugMode::Debug);
+    /// assert_eq!("2".parse::<DebugMode>().unwrap(), DebugMode::Full);
+    /// assert!("verbose".parse::<DebugMode>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.trim().to_lowercase();
+        match s.as_str() {
+            "friendly" | "humanfriendly" | "human-friendly" => Ok(Self::HumanFriendly),
+            "debug"                                         => Ok(Self::Debug),
+            "full" | "trace"                                => Ok(Self::Full),
+            num => match u32::from_str(num) {
+                Ok(num) => Ok(Self::from_num(num)),
+                Err(_)  => Err(DebugModeParseError { raw: s }),
+            },
+        }
+    }
 }
 
 
@@ -265,32 +395,115 @@ pub enum ColourChoice {
     /// ANSI colours are never written, regardless of what we write to.
     No,
     /// ANSI colours are written depending on whether we are writing to a TTY (in which case we will), or some other output (in which case we won't).
+    /// 
+    /// This also follows the [NO_COLOR](https://no-color.org) and [CLICOLOR](https://bixense.com/clicolors) conventions. Specifically, the following are checked in order, and the first that applies decides:
+    /// 1. If `NO_COLOR` is set to a non-empty value, colours are disabled.
+    /// 2. If `CLICOLOR_FORCE` is set to a non-empty value other than `0`, colours are enabled (even if we're not writing to a TTY).
+    /// 3. If `TERM` is `dumb`, colours are disabled.
+    /// 4. If `CLICOLOR` is `0`, colours are disabled.
+    /// 5. Otherwise, colours are enabled only if we're writing to a TTY.
     Auto,
 }
 impl ColourChoice {
-    /// Resolves this ColourChoice based on the given writer.
+    /// Resolves this ColourChoice based on the given TTY-status and the process' environment.
+    /// 
+    /// # Arguments
+    /// - `is_terminal`: Whether the output we resolve for is a TTY (see [`is_terminal()`]).
+    /// 
+    /// # Returns
+    /// True if self was (`ColourChoice::Yes` || (`ColourChoice::Auto` && the environment and the output allow it)), or else false.
+    #[inline]
+    fn resolve(&self, is_terminal: bool) -> bool {
+        self.resolve_with(is_terminal, |name| std::env::var_os(name).map(|value| value.to_string_lossy().into_owned()))
+    }
+
+    /// Resolves this ColourChoice based on the given TTY-status and environment.
+    /// 
+    /// See `ColourChoice::Auto` for the precedence of the environment variables.
     /// 
     /// # Arguments
-    /// - `writer`: The writer to resolve the ColourChoice with.
+    /// - `is_terminal`: Whether the output we resolve for is a TTY.
+    /// - `env`: A function that returns the value of the environment variable with the given name, if it is set. This allows the environment to be injected instead of read from the process.
     /// 
     /// # Returns
-    /// True if self was (`ColourChoice::Yes` || (`ColourChoice::Auto` && `writer` is TTY)), or else false.
-    fn resolve(&self, writer: &(impl 'static + Write)) -> bool {
+    /// True if ANSI colours should be written, or else false.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use humanlog::ColourChoice;
+    /// 
+    /// // Resolves in the given environment
+    /// fn resolve(choice: ColourChoice, is_terminal: bool, env: &[(&str, &str)]) -> bool {
+    ///     let env: HashMap<&str, &str> = env.iter().copied().collect();
+    ///     choice.resolve_with(is_terminal, |name| env.get(name).map(|value| value.to_string()))
+    /// }
+    /// 
+    /// // Explicit choices ignore the environment
+    /// assert!(resolve(ColourChoice::Yes, false, &[ ("NO_COLOR", "1") ]));
+    /// assert!(!resolve(ColourChoice::No, true, &[ ("CLICOLOR_FORCE", "1") ]));
+    /// 
+    /// // Without any variables, it depends on the terminal
+    /// assert!(resolve(ColourChoice::Auto, true, &[]));
+    /// assert!(!resolve(ColourChoice::Auto, false, &[]));
+    /// 
+    /// // `NO_COLOR` beats everything, but only if non-empty
+    /// assert!(!resolve(ColourChoice::Auto, true, &[ ("NO_COLOR", "1") ]));
+    /// assert!(!resolve(ColourChoice::Auto, true, &[ ("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1") ]));
+    /// assert!(resolve(ColourChoice::Auto, true, &[ ("NO_COLOR", "") ]));
+    /// 
+    /// // `CLICOLOR_FORCE` enables colours even without a terminal, unless it's `0`
+    /// assert!(resolve(ColourChoice::Auto, false, &[ ("CLICOLOR_FORCE", "1") ]));
+    /// assert!(resolve(ColourChoice::Auto, false, &[ ("CLICOLOR_FORCE", "1"), ("TERM", "dumb"), ("CLICOLOR", "0") ]));
+    /// assert!(!resolve(ColourChoice::Auto, false, &[ ("CLICOLOR_FORCE", "0") ]));
+    /// 
+    /// // `TERM=dumb` and `CLICOLOR=0` disable colours on terminals
+    /// assert!(!resolve(ColourChoice::Auto, true, &[ ("TERM", "dumb") ]));
+    /// assert!(resolve(ColourChoice::Auto, true, &[ ("TERM", "xterm-256color") ]));
+    /// assert!(!resolve(ColourChoice::Auto, true, &[ ("CLICOLOR", "0") ]));
+    /// assert!(resolve(ColourChoice::Auto, true, &[ ("CLICOLOR", "1") ]));
+    /// assert!(!resolve(ColourChoice::Auto, false, &[ ("CLICOLOR", "1") ]));
+    /// ```
+    pub fn resolve_with(&self, is_terminal: bool, env: impl Fn(&str) -> Option<String>) -> bool {
         match self {
             // Easy cases first
             ColourChoice::Yes => true,
             ColourChoice::No  => false,
 
             // Then the hard one
-            ColourChoice::Auto => if (writer as &dyn Any).downcast_ref::<Stdin>().is_some() {
-                std::io::stdin().is_terminal()
-            } else if (writer as &dyn Any).downcast_ref::<Stderr>().is_some() {
-                std::io::stderr().is_terminal()
-            } else if (writer as &dyn Any).downcast_ref::<Stdout>().is_some() {
-                std::io::stdout().is_terminal()
-            } else {
-                false
-            }
+            ColourChoice::Auto => {
+                if env("NO_COLOR").map(|value| !value.is_empty()).unwrap_or(false) { return false; }
+                if env("CLICOLOR_FORCE").map(|value| !value.is_empty() && value != "0").unwrap_or(false) { return true; }
+                if env("TERM").map(|value| value == "dumb").unwrap_or(false) { return false; }
+                if env("CLICOLOR").map(|value| value == "0").unwrap_or(false) { return false; }
+                is_terminal
+            },
+        }
+    }
+}
+
+impl FromStr for ColourChoice {
+    type Err = ColourChoiceParseError;
+
+    /// Parses a ColourChoice from a string.
+    /// 
+    /// Accepts (case-insensitive) `auto`; `always`, `yes`, `true` or `1`; and `never`, `no`, `false` or `0`.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::ColourChoice;
+    /// 
+    /// assert_eq!("auto".parse::<ColourChoice>().unwrap(), ColourChoice::Auto);
+    /// assert_eq!("always".parse::<ColourChoice>().unwrap(), ColourChoice::Yes);
+    /// assert_eq!("Never".parse::<ColourChoice>().unwrap(), ColourChoice::No);
+    /// assert!("sometimes".parse::<ColourChoice>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto"                             => Ok(Self::Auto),
+            "always" | "yes" | "true" | "1"    => Ok(Self::Yes),
+            "never" | "no" | "false" | "0"     => Ok(Self::No),
+            _                                  => Err(ColourChoiceParseError { raw: s.into() }),
         }
     }
 }
@@ -301,6 +514,8 @@ impl ColourChoice {
 /// 
 /// This can be used to customize the output source of the HumanLogger.
 /// 
+/// Every record is rendered completely before it's written, and then given to the writer with a single `write_all()`. For writers that pass this on as-is (like `Stdout`, `Stderr`, `File`s and [`RotatingFile`]s), this means that a record of up to `PIPE_BUF` bytes (at least 512, and 4096 on Linux) is written with a single `write()`, which is atomic for pipes and for files opened in append mode (see `OpenOptions::append()`). As such, the records of multiple processes sharing a stderr pipe or log file are never interleaved mid-line. Longer records and writers that buffer (e.g., a `BufWriter`) do not have this guarantee.
+/// 
 /// # Examples
 /// 
 /// A logger that writes to stdout only, instead of mixed stdout/stderr (like usual).
@@ -329,7 +544,7 @@ impl ColourChoice {
 ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
 ///         }
 ///     },
-///
+/// 
 ///     Err(err) => {
 ///         eprintln!("WARNING: Failed to initialize logger: Failed to create file 'output.log': {err} (no logging enabled for this session)");
 ///     },
@@ -364,13 +579,27 @@ impl ColourChoice {
 /// ```
 pub struct LogWriter {
     /// The debug label of this writer.
-    label  : String,
+    label       : String,
     /// The writer to write to.
-    writer : Box<dyn Send + Sync + Write>,
+    writer      : Box<dyn Send + Sync + Write>,
+    /// Whether the writer is a TTY, which is needed to resolve `ColourChoice::Auto`.
+    is_terminal : bool,
     /// Whether to write to this writer with ANSI.
-    colour : bool,
-    /// The set of filters to allow.
-    filter : Vec<Level>,
+    colour      : bool,
+    /// The formatter that decides how messages are written.
+    formatter   : Box<dyn Formatter>,
+    /// Any per-target directives that override those of the HumanLogger for this writer.
+    directives  : Directives,
+    /// What to do if writing to this writer fails.
+    policy      : ErrorPolicy,
+    /// The style in which to write timestamps, if it overrides that of the HumanLogger.
+    timestamp   : Option<TimestampStyle>,
+    /// Which thread and process fields to write, if it overrides that of the HumanLogger.
+    fields      : Option<PrefixFields>,
+    /// The levels to write.
+    filter      : LevelSet,
+    /// A closure that decides which of the records that pass all other filters are written, if any.
+    predicate   : Option<Predicate>,
 }
 impl LogWriter {
     /// Default constructor for the LogWriter that initializes it for stdout.
@@ -413,10 +642,12 @@ impl LogWriter {
 
     /// Constructor for the LogWriter that wraps it around the given `Write`r.
     /// 
+    /// This is a shorthand for using [`LogWriter::builder()`], which can also set the other options of the writer.
+    /// 
     /// # Arguments
     /// - `writer`: The handle or other object that implement `Write` and we will write to.
     /// - `colour_choice`: Whether to enable ANSI colours for this file or not.
-    /// - `filter`: The list of Levels that are only allowed to be written to this writer.
+    /// - `filter`: The levels that are only allowed to be written to this writer, e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels (see [`LevelSet`]).
     /// - `label`: Some description of the writer for debugging purposes.
     /// 
     /// # Returns
@@ -434,65 +665,568 @@ impl LogWriter {
     /// }
     /// ```
     #[inline]
-    pub fn new(writer: impl 'static + Send + Sync + Write, colour: ColourChoice, filter: impl Into<Vec<Level>>, label: impl Into<String>) -> Self {
-        // Resolve the colour first
-        let colour: bool = colour.resolve(&writer);
+    pub fn new(writer: impl 'static + Send + Sync + Write, colour: ColourChoice, filter: impl Into<LevelSet>, label: impl Into<String>) -> Self {
+        Self::builder(writer).with_colour(colour).with_levels(filter).with_label(label).build()
+    }
 
-        // Return ourselves with that colour
-        Self {
-            label  : label.into(),
-            writer : Box::new(writer),
-            colour,
-            filter : filter.into(),
-        }
+    /// Constructor for the LogWriter that writes to a file which is rotated according to the given policy.
+    /// 
+    /// The file is opened in append mode, and never written with ANSI colours. See [`RotatingFile`] for more information.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the file to write to.
+    /// - `policy`: The `RotationPolicy` that determines when and how to rotate the file.
+    /// - `filter`: The levels that are only allowed to be written to this writer, e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels (see [`LevelSet`]).
+    /// - `label`: Some description of the writer for debugging purposes.
+    /// 
+    /// # Returns
+    /// A new LogWriter instance that writes to a rotating file.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to open the file.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, LogWriter, RotationInterval, RotationPolicy};
+    /// use log::Level;
+    /// 
+    /// // Log everything to a file that is rotated daily, keeping a week's worth of logs
+    /// match LogWriter::rotating("output.log", RotationPolicy::new().with_interval(RotationInterval::Daily).with_keep(7), vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file") {
+    ///     Ok(file_logger) => {
+    ///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+    ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         }
+    ///     },
+    /// 
+    ///     Err(err) => {
+    ///         eprintln!("WARNING: Failed to initialize logger: Failed to open file 'output.log': {err} (no logging enabled for this session)");
+    ///     },
+    /// }
+    /// ```
+    #[inline]
+    pub fn rotating(path: impl Into<PathBuf>, policy: RotationPolicy, filter: impl Into<LevelSet>, label: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self::new(RotatingFile::open(path, policy)?, ColourChoice::No, filter, label))
+    }
+
+    /// Constructor for the LogWriter that sends records to a syslog daemon.
+    /// 
+    /// Records are written with the given [`SyslogFormatter`] and never with colours. Every record is sent as a separate message.
+    /// 
+    /// # Arguments
+    /// - `transport`: The `SyslogTransport` that determines where to send records to (e.g., `SyslogTransport::default()` for the local daemon at `/dev/log`).
+    /// - `formatter`: The `SyslogFormatter` that determines the facility, app-name, procid and framing of the messages.
+    /// - `filter`: The levels that are only allowed to be written to this writer, e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels (see [`LevelSet`]).
+    /// - `label`: Some description of the writer for debugging purposes.
+    /// 
+    /// # Returns
+    /// A new LogWriter instance that writes to syslog.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to connect to the syslog daemon.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, LogWriter, SyslogFacility, SyslogFormatter, SyslogTransport};
+    /// use log::Level;
+    /// 
+    /// // Send everything but trace records to a remote daemon
+    /// match LogWriter::syslog(SyslogTransport::Udp("localhost:514".into()), SyslogFormatter::new().with_facility(SyslogFacility::Daemon), vec![ Level::Error, Level::Warn, Level::Info, Level::Debug ], "syslog") {
+    ///     Ok(syslog_logger) => {
+    ///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), syslog_logger ], DebugMode::Debug).init() {
+    ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         }
+    ///     },
+    /// 
+    ///     Err(err) => {
+    ///         eprintln!("WARNING: Failed to initialize logger: Failed to connect to syslog: {err} (no logging enabled for this session)");
+    ///     },
+    /// }
+    /// ```
+    #[inline]
+    pub fn syslog(transport: SyslogTransport, formatter: SyslogFormatter, filter: impl Into<LevelSet>, label: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self::new(SyslogWriter::connect(&transport)?, ColourChoice::No, filter, label).with_formatter(formatter))
+    }
+
+    /// Constructor for the LogWriter that sends records to the systemd journal, using its native protocol.
+    /// 
+    /// Records are written with the given [`JournaldFormatter`], which sends the level, location and key/value pairs as separate fields. Only available on Linux.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of journald's socket. This is typically [`journald::JOURNALD_SOCKET`].
+    /// - `formatter`: The `JournaldFormatter` that determines the identifier of the records.
+    /// - `filter`: The levels that are only allowed to be written to this writer, e.g., a `Vec<Level>`, a `LevelFilter` or a range of levels (see [`LevelSet`]).
+    /// - `label`: Some description of the writer for debugging purposes.
+    /// 
+    /// # Returns
+    /// A new LogWriter instance that writes to the journal.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to connect to journald.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, JournaldFormatter, LogWriter};
+    /// use humanlog::journald::JOURNALD_SOCKET;
+    /// use log::Level;
+    /// 
+    /// // Log to the journal when running as a systemd unit, or to the terminal otherwise
+    /// let writers: Vec<LogWriter> = match LogWriter::journald(JOURNALD_SOCKET, JournaldFormatter::new(), vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "journald") {
+    ///     Ok(journald_logger) if std::env::var_os("JOURNAL_STREAM").is_some() => vec![ journald_logger ],
+    ///     _                                                                     => vec![ LogWriter::stdout(), LogWriter::stderr() ],
+    /// };
+    /// if let Err(err) = HumanLogger::new(writers, DebugMode::Debug).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn journald(path: impl AsRef<std::path::Path>, formatter: JournaldFormatter, filter: impl Into<LevelSet>, label: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self::new(JournaldWriter::connect(path)?, ColourChoice::No, filter, label).with_formatter(formatter))
+    }
+
+    /// Changes the [`Formatter`] with which this LogWriter writes its messages.
+    /// 
+    /// By default, all LogWriters use the [`PrettyFormatter`], which writes in the layout matching the HumanLogger's `DebugMode`.
+    /// 
+    /// # Arguments
+    /// - `formatter`: The new `Formatter` to write messages with.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new formatter set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::fs::File;
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, JsonFormatter, LogWriter};
+    /// use log::Level;
+    /// 
+    /// // Open a file
+    /// match File::create("output.jsonl") {
+    ///     Ok(handle) => {
+    ///         // Write machine-readable JSON Lines to the file, while the terminal keeps the pretty format
+    ///         let file_logger: LogWriter = LogWriter::new(handle, ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file").with_formatter(JsonFormatter);
+    ///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+    ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         }
+    ///     },
+    /// 
+    ///     Err(err) => {
+    ///         eprintln!("WARNING: Failed to initialize logger: Failed to create file 'output.jsonl': {err} (no logging enabled for this session)");
+    ///     },
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_formatter(mut self, formatter: impl 'static + Formatter) -> Self {
+        self.formatter = Box::new(formatter);
+        self
+    }
+
+    /// Sets `RUST_LOG`-style directives that decide which levels are written to this writer per target.
+    /// 
+    /// These take precedence over the directives of the HumanLogger for this writer. Targets matched by neither fall back to the level implied by the `DebugMode`. Note that the writer's level filter is still applied on top of this.
+    /// 
+    /// # Arguments
+    /// - `directives`: The `Directives` to apply to this writer.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new directives set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::fs::File;
+    /// use humanlog::{ColourChoice, DebugMode, Directives, HumanLogger, LogWriter};
+    /// use log::Level;
+    /// 
+    /// // Open a file
+    /// match File::create("output.log") {
+    ///     Ok(handle) => {
+    ///         // Write everything from our own crate to the file, but keep the terminal quiet-ish
+    ///         let file_logger: LogWriter = LogWriter::new(handle, ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file").with_directives("myapp=trace".parse().unwrap());
+    ///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+    ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         }
+    ///     },
+    /// 
+    ///     Err(err) => {
+    ///         eprintln!("WARNING: Failed to initialize logger: Failed to create file 'output.log': {err} (no logging enabled for this session)");
+    ///     },
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_directives(mut self, directives: Directives) -> Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Changes what happens when writing to this LogWriter fails.
+    /// 
+    /// By default, the writer is disabled for the rest of the session (`ErrorPolicy::Disable`). See the [`ErrorPolicy`] for the alternatives.
+    /// 
+    /// # Arguments
+    /// - `policy`: The `ErrorPolicy` to apply to this writer.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new error policy set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    /// use humanlog::{DebugMode, ErrorPolicy, HumanLogger, LogWriter};
+    /// 
+    /// // Don't give up on stdout just because it was temporarily unavailable
+    /// let stdout: LogWriter = LogWriter::stdout().with_error_policy(ErrorPolicy::retry(Duration::from_millis(100), Duration::from_secs(10)));
+    /// if let Err(err) = HumanLogger::new(vec![ stdout, LogWriter::stderr() ], DebugMode::Debug).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Changes the style in which this LogWriter writes timestamps, overriding that of the HumanLogger.
+    /// 
+    /// By default, LogWriters use the style set with `HumanLogger::with_timestamp()`.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The `TimestampStyle` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new timestamp style set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::fs::File;
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter, TimestampFormat, TimestampPrecision, TimestampStyle};
+    /// use log::Level;
+    /// 
+    /// // Write precise UTC timestamps to the file, but keep the terminal output as-is
+    /// match File::create("output.log") {
+    ///     Ok(handle) => {
+    ///         let file_logger: LogWriter = LogWriter::new(handle, ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file").with_timestamp(TimestampStyle::utc().with_format(TimestampFormat::Rfc3339(TimestampPrecision::Micros)));
+    ///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+    ///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         }
+    ///     },
+    /// 
+    ///     Err(err) => {
+    ///         eprintln!("WARNING: Failed to initialize logger: Failed to create file 'output.log': {err} (no logging enabled for this session)");
+    ///     },
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_timestamp(mut self, timestamp: TimestampStyle) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Changes which thread and process fields this LogWriter writes, overriding those of the HumanLogger.
+    /// 
+    /// By default, LogWriters use the fields set with `HumanLogger::with_fields()`.
+    /// 
+    /// # Arguments
+    /// - `fields`: The `PrefixFields` to write.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new fields set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, JsonFormatter, LogWriter, PrefixFields};
+    /// use log::Level;
+    /// 
+    /// // Keep the terminal output short, but record everything in the JSON logs
+    /// let json_logger: LogWriter = LogWriter::new(std::io::stdout(), ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "json").with_formatter(JsonFormatter).with_fields(PrefixFields::all());
+    /// if let Err(err) = HumanLogger::new(vec![ LogWriter::stderr(), json_logger ], DebugMode::Debug).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_fields(mut self, fields: PrefixFields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Only writes records to this LogWriter for which the given closure returns true.
+    /// 
+    /// This is applied on top of the writer's levels and directives, and can decide based on anything in the record's `Metadata` (i.e., its level and target).
+    /// 
+    /// # Arguments
+    /// - `predicate`: The closure that decides whether a record is written.
+    /// 
+    /// # Returns
+    /// The same LogWriter as `self`, but with the new predicate set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter};
+    /// use humanlog::capture::MemoryWriter;
+    /// use log::{Level, LevelFilter, Log as _, Record};
+    /// 
+    /// // Only write audit records to this writer
+    /// let memory: MemoryWriter = MemoryWriter::new();
+    /// let audit: LogWriter = LogWriter::new(memory.clone(), ColourChoice::No, LevelFilter::Info, "audit").with_predicate(|metadata| metadata.target() == "audit");
+    /// let logger: HumanLogger = HumanLogger::new(vec![ audit ], DebugMode::HumanFriendly);
+    /// logger.log(&Record::builder().level(Level::Warn).target("audit").args(format_args!("User 42 failed to log in")).build());
+    /// logger.log(&Record::builder().level(Level::Warn).target("myapp").args(format_args!("Hello there!")).build());
+    /// assert_eq!(memory.contents(), "WARNING: User 42 failed to log in\n");
+    /// ```
+    #[inline]
+    pub fn with_predicate(mut self, predicate: impl 'static + Send + Sync + Fn(&Metadata) -> bool) -> Self {
+        self.predicate = Some(Box::new(predicate));
+        self
     }
 }
 
-/// An inner counterpart of LogWriter that does not carry filter information anymore.
+/// A writer that is shared between the lists of all the levels it writes, together with its error state.
+type SharedWriter = Arc<Mutex<(WriterState, InternalLogWriter)>>;
+
+/// The identifier of the next [`InternalLogWriter`] or [`Capture`](capture::Capture), with which scopes remember who they were introduced to.
+static NEXT_WRITER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A closure that decides whether a record is written to a writer, based on its metadata.
+type Predicate = Box<dyn Send + Sync + Fn(&Metadata) -> bool>;
+
+/// An inner counterpart of LogWriter that does not carry level filter information anymore.
 struct InternalLogWriter {
+    /// An identifier that is unique to this writer, used to remember which scopes it introduced.
+    id          : usize,
     /// The debug label of this writer.
-    label  : String,
+    label       : String,
     /// The writer to write to.
-    writer : Box<dyn Send + Sync + Write>,
+    writer      : Sink,
+    /// Whether the writer is a TTY, which is needed to resolve `ColourChoice::Auto`.
+    is_terminal : bool,
     /// Whether to write to this writer with ANSI.
-    colour : bool,
+    colour      : bool,
+    /// The formatter that decides how messages are written.
+    formatter   : Box<dyn Formatter>,
+    /// Any per-target directives that override those of the HumanLogger for this writer.
+    directives  : Directives,
+    /// What to do if writing to this writer fails.
+    policy      : ErrorPolicy,
+    /// The style in which to write timestamps, if it overrides that of the HumanLogger.
+    timestamp   : Option<TimestampStyle>,
+    /// Which thread and process fields to write, if it overrides that of the HumanLogger.
+    fields      : Option<PrefixFields>,
+    /// A closure that decides which of the records that pass all other filters are written, if any.
+    predicate   : Option<Predicate>,
 }
 impl From<LogWriter> for InternalLogWriter {
     #[inline]
     fn from(value: LogWriter) -> Self {
         Self {
-            label  : value.label,
-            writer : value.writer,
-            colour : value.colour,
+            id          : NEXT_WRITER_ID.fetch_add(1, Ordering::Relaxed),
+            label       : value.label,
+            writer      : Sink::Direct(value.writer),
+            is_terminal : value.is_terminal,
+            colour      : value.colour,
+            formatter   : value.formatter,
+            directives  : value.directives,
+            policy      : value.policy,
+            timestamp   : value.timestamp,
+            fields      : value.fields,
+            predicate   : value.predicate,
         }
     }
 }
 
 
-
-
-
-/***** LIBRARY *****/
-/// Defines a logger that has a pretty, user-friendly mode, and a comprehensive, dev-friendly  _debug_ mode.
-pub struct HumanLogger {
+/// The writers and settings of a [`HumanLogger`] that may be changed after it has been installed.
+struct Config {
     /// All writers that write `Level::Error`.
-    error_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>>,
+    error_writers : Vec<SharedWriter>,
     /// All writers that write `Level::Warn`.
-    warn_writers  : Vec<Arc<Mutex<(bool, InternalLogWriter)>>>,
+    warn_writers  : Vec<SharedWriter>,
     /// All writers that write `Level::Info`.
-    info_writers  : Vec<Arc<Mutex<(bool, InternalLogWriter)>>>,
+    info_writers  : Vec<SharedWriter>,
     /// All writers that write `Level::Debug`.
-    debug_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>>,
+    debug_writers : Vec<SharedWriter>,
     /// All writers that write `Level::Trace`.
-    trace_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>>,
+    trace_writers : Vec<SharedWriter>,
 
     /// Which debug mode to log with.
-    debug : DebugMode,
+    debug      : DebugMode,
+    /// Any per-target directives that override the levels implied by the debug mode.
+    directives : Directives,
+    /// How to write timestamps.
+    timestamp  : TimestampStyle,
+    /// How to lay out the continuation lines of multi-line messages.
+    multiline  : MultilineStyle,
+    /// The labels and colours to write records with.
+    theme      : Theme,
+    /// How to align the columns of the prefix of records.
+    alignment  : Alignment,
+    /// Which thread and process fields to write.
+    fields     : PrefixFields,
+
+    /// A summary of the above that decides which records are enabled without locking the writers.
+    gate       : Arc<Gate>,
+}
+impl Config {
+    /// Updates the [`Gate`] after the writers, their state, the debug mode or any directives changed.
+    /// 
+    /// This locks every writer, so should not be called while holding one.
+    #[inline]
+    fn update_gate(&self) { self.gate.update(self); }
+
+    /// Returns the writers that want to receive records of the given level.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` of the record.
+    /// 
+    /// # Returns
+    /// A slice with the writers that are registered for this level.
+    #[inline]
+    fn writers(&self, level: Level) -> &[SharedWriter] {
+        match level {
+            Level::Error => &self.error_writers,
+            Level::Warn  => &self.warn_writers,
+            Level::Info  => &self.info_writers,
+            Level::Debug => &self.debug_writers,
+            Level::Trace => &self.trace_writers,
+        }
+    }
+
+    /// Returns every writer once, even if it's registered for multiple levels.
+    /// 
+    /// # Returns
+    /// A list of references to the unique writers, in the order in which they were given.
+    fn unique_writers(&self) -> Vec<&SharedWriter> {
+        let mut unique: Vec<&SharedWriter> = vec![];
+        for level in [ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ] {
+            for w in self.writers(level) {
+                if !unique.iter().any(|u| Arc::ptr_eq(u, w)) { unique.push(w); }
+            }
+        }
+        unique
+    }
+
+    /// Registers the given writer for the given levels.
+    /// 
+    /// # Arguments
+    /// - `writer`: The writer to register.
+    /// - `filter`: The levels to register it for.
+    fn add(&mut self, writer: SharedWriter, filter: LevelSet) {
+        for level in filter.iter() {
+            let writers: &mut Vec<SharedWriter> = match level {
+                Level::Error => &mut self.error_writers,
+                Level::Warn  => &mut self.warn_writers,
+                Level::Info  => &mut self.info_writers,
+                Level::Debug => &mut self.debug_writers,
+                Level::Trace => &mut self.trace_writers,
+            };
+            if !writers.iter().any(|w| Arc::ptr_eq(w, &writer)) { writers.push(writer.clone()); }
+        }
+    }
+
+    /// Unregisters the given writer for all levels.
+    /// 
+    /// # Arguments
+    /// - `writer`: The writer to unregister.
+    fn remove(&mut self, writer: &SharedWriter) {
+        for writers in [ &mut self.error_writers, &mut self.warn_writers, &mut self.info_writers, &mut self.debug_writers, &mut self.trace_writers ] {
+            writers.retain(|w| !Arc::ptr_eq(w, writer));
+        }
+    }
+
+    /// Returns the writers that are currently not written to because writing to them failed.
+    /// 
+    /// # Returns
+    /// A [`WriterStatus`] for every such writer.
+    fn disabled_writers(&self) -> Vec<WriterStatus> {
+        self.unique_writers().into_iter().filter_map(|w| {
+            let lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+            lock.0.status(&lock.1.label)
+        }).collect()
+    }
+
+    /// Computes the most verbose level that the given writer will write for the given target.
+    /// 
+    /// # Arguments
+    /// - `writer`: The `InternalLogWriter` who's directives to consider first.
+    /// - `target`: The target of the record.
+    /// 
+    /// # Returns
+    /// The `LevelFilter` that applies to the target, which is determined by the writer's directives, then by our own directives and finally by the debug mode.
+    #[inline]
+    fn level_for(&self, writer: &InternalLogWriter, target: &str) -> LevelFilter {
+        writer.directives.level_for(target).or_else(|| self.directives.level_for(target)).unwrap_or_else(|| self.debug.level_filter())
+    }
+
+    /// Computes the most verbose level that any writer will ever write.
+    /// 
+    /// This is used to set `log`'s global maximum level as tightly as possible.
+    /// 
+    /// # Returns
+    /// The `LevelFilter` that is the maximum of what all writers accept, given their level filters and the active directives.
+    fn max_level(&self) -> LevelFilter {
+        // Find out what our own directives allow, falling back to the debug mode if there's no default
+        let mut logger_max: LevelFilter = self.directives.max_level();
+        if self.directives.default_level().is_none() { logger_max = logger_max.max(self.debug.level_filter()); }
+
+        // Then find the maximum for every writer, taking their filters into account
+        let mut max: LevelFilter = LevelFilter::Off;
+        for level in [ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ] {
+            for w in self.writers(level) {
+                let lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+                let mut writer_max: LevelFilter = lock.1.directives.max_level();
+                if lock.1.directives.default_level().is_none() { writer_max = writer_max.max(logger_max); }
+                if level <= writer_max { max = max.max(level.to_level_filter()); }
+            }
+        }
+        max
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The configuration of the logger installed with [`HumanLogger::init()`], if any.
+static INSTALLED: OnceLock<Arc<RwLock<Config>>> = OnceLock::new();
+
+/// Returns the writers of the installed logger that are currently not written to because writing to them failed.
+/// 
+/// # Returns
+/// A [`WriterStatus`] for every writer that is disabled or waiting to be retried. Empty if no HumanLogger has been installed with `HumanLogger::init()`.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger};
+/// 
+/// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).init() {
+///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+/// }
+/// 
+/// // Nothing went wrong yet
+/// assert!(humanlog::disabled_writers().is_empty());
+/// ```
+pub fn disabled_writers() -> Vec<WriterStatus> {
+    INSTALLED.get().map(|config| config.read().disabled_writers()).unwrap_or_default()
+}
+
+
+
+/// Defines a logger that has a pretty, user-friendly mode, and a comprehensive, dev-friendly  _debug_ mode.
+pub struct HumanLogger {
+    /// The writers and settings, which are shared with any [`LoggerHandle`]s.
+    config       : Arc<RwLock<Config>>,
+    /// The summary of the config that decides which records are enabled, which we can read without locking it.
+    gate         : Arc<Gate>,
+    /// The capacity and overflow policy of the writer thread, if we log asynchronously.
+    asynchronous : Option<(usize, OverflowPolicy)>,
+    /// The writer thread, once started.
+    background   : Option<Arc<Background>>,
 }
 
 impl HumanLogger {
     /// Constructor for the HumanLogger that will log to the given set of `Write`rs.
     /// 
+    /// This is a shorthand for using [`HumanLogger::builder()`], which can also set the other options of the logger.
+    /// 
     /// Don't forget to also install the Logger at some point using `HumanLogger::init()`.
     /// 
     /// # Arguments
@@ -511,41 +1245,8 @@ impl HumanLogger {
     ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
     /// }
     /// ```
-    pub fn new(writers: impl IntoIterator<Item = LogWriter>, debug: DebugMode) -> Self {
-        // Sort the given writers into the given lists
-        let mut error_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>> = vec![];
-        let mut warn_writers  : Vec<Arc<Mutex<(bool, InternalLogWriter)>>> = vec![];
-        let mut info_writers  : Vec<Arc<Mutex<(bool, InternalLogWriter)>>> = vec![];
-        let mut debug_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>> = vec![];
-        let mut trace_writers : Vec<Arc<Mutex<(bool, InternalLogWriter)>>> = vec![];
-        for writer in writers.into_iter() {
-            // Create the base arc
-            let filters : Vec<Level> = writer.filter.clone();
-            let writer  : Arc<Mutex<(bool, InternalLogWriter)>> = Arc::new(Mutex::new((true, writer.into())));
-
-            // Add it to any list it wants
-            for filter in filters {
-                match filter {
-                    Level::Error => error_writers.push(writer.clone()),
-                    Level::Warn  => warn_writers.push(writer.clone()),
-                    Level::Info  => info_writers.push(writer.clone()),
-                    Level::Debug => debug_writers.push(writer.clone()),
-                    Level::Trace => trace_writers.push(writer.clone()),
-                }
-            }
-        }
-
-        // We can now store this
-        Self {
-            error_writers,
-            warn_writers,
-            info_writers,
-            debug_writers,
-            trace_writers,
-
-            debug,
-        }
-    }
+    #[inline]
+    pub fn new(writers: impl IntoIterator<Item = LogWriter>, debug: DebugMode) -> Self { Self::builder().with_mode(debug).with_writers(writers).build() }
 
     /// Default constructor for the HumanLogger that prepares it for logging to the terminal.
     /// 
@@ -575,6 +1276,267 @@ impl HumanLogger {
 
 
 
+    /// Sets `RUST_LOG`-style directives that decide which levels are logged per target.
+    /// 
+    /// Targets that are not matched by any directive (and if there is no default level in the directives) fall back to the level implied by the `DebugMode`. Individual `LogWriter`s may override these with `LogWriter::with_directives()`.
+    /// 
+    /// # Arguments
+    /// - `directives`: The `Directives` to apply.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new directives set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger};
+    /// 
+    /// // Trace our own crate, but keep noisy dependencies down
+    /// let logger: HumanLogger = HumanLogger::terminal(DebugMode::Full).with_directives("myapp=trace,hyper=warn,rustls=warn,info".parse().unwrap());
+    /// if let Err(err) = logger.init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_directives(self, directives: Directives) -> Self {
+        let mut config: RwLockWriteGuard<Config> = self.config.write();
+        config.directives = directives;
+        config.update_gate();
+        drop(config);
+        self
+    }
+
+    /// Sets the style in which timestamps are written.
+    /// 
+    /// By default, timestamps are written in local time, in a format matching the `DebugMode`. Individual writers can override this with `LogWriter::with_timestamp()`.
+    /// 
+    /// # Arguments
+    /// - `timestamp`: The `TimestampStyle` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new timestamp style set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, TimestampStyle};
+    /// 
+    /// // Don't write timestamps, e.g., because some other service adds them already
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_timestamp(TimestampStyle::none()).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_timestamp(self, timestamp: TimestampStyle) -> Self {
+        self.config.write().timestamp = timestamp;
+        self
+    }
+
+    /// Sets how the continuation lines of messages that span multiple lines are laid out.
+    /// 
+    /// By default, they are written as-is (`MultilineStyle::Verbatim`), which means they start at the first column.
+    /// 
+    /// # Arguments
+    /// - `multiline`: The `MultilineStyle` to lay out continuation lines in.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new multiline style set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, MultilineStyle};
+    /// 
+    /// // Align continuation lines with the start of the message, e.g., for pretty-printed structs
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_multiline(MultilineStyle::Indent).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_multiline(self, multiline: MultilineStyle) -> Self {
+        self.config.write().multiline = multiline;
+        self
+    }
+
+    /// Sets the labels and colours with which records are written.
+    /// 
+    /// By default, `Theme::new()` is used. See the [`Theme`] for the available presets.
+    /// 
+    /// # Arguments
+    /// - `theme`: The `Theme` to write records with.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new theme set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, Theme};
+    /// 
+    /// // Line up the messages by giving every level a label of the same width
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_theme(Theme::fixed_width()).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_theme(self, theme: Theme) -> Self {
+        self.config.write().theme = theme;
+        self
+    }
+
+    /// Aligns the columns of the prefix written in `DebugMode::Debug` and `DebugMode::Full`, so that messages start at the same column.
+    /// 
+    /// By default, no columns are aligned. See the [`Alignment`] for more information.
+    /// 
+    /// # Arguments
+    /// - `alignment`: The `Alignment` that decides the widths of the columns.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new alignment set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{Alignment, DebugMode, HumanLogger};
+    /// 
+    /// // Pad the level and target, and elide targets longer than 16 characters
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_alignment(Alignment::new().with_level_width(7).with_target_width(16)).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        self.config.write().alignment = alignment;
+        self
+    }
+
+    /// Sets which fields about the thread and process that logged a record are written, e.g., to tell apart the threads in `DebugMode::Full`.
+    /// 
+    /// By default, none are written. Individual writers can override this with `LogWriter::with_fields()`. See the [`PrefixFields`] for how every formatter writes them.
+    /// 
+    /// # Arguments
+    /// - `fields`: The `PrefixFields` to write.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but with the new fields set.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, PrefixFields};
+    /// 
+    /// // Writes lines like `[2023-03-03T18:11:37.853292702+01:00 worker-1 ERROR src/main.rs:27 app] Deadlock!`
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Full).with_fields(PrefixFields::none().with_thread(true)).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_fields(self, fields: PrefixFields) -> Self {
+        self.config.write().fields = fields;
+        self
+    }
+
+    /// Makes the logger write asynchronously, i.e., on a dedicated writer thread.
+    /// 
+    /// Records are still formatted on the thread that logs them, but are then handed to the writer thread over a bounded queue. This keeps slow files or pipes from stalling the rest of the program. The writer thread is started by `HumanLogger::init()`.
+    /// 
+    /// The queue is drained when the logger is flushed (e.g., with `log::logger().flush()`) and when the process exits normally (i.e., by returning from `main()` or calling `std::process::exit()`). If records were dropped because the queue was full, a warning saying how many is logged as soon as there is space again.
+    /// 
+    /// # Arguments
+    /// - `capacity`: The maximum number of records that can be waiting in the queue at once.
+    /// - `overflow`: The `OverflowPolicy` that decides what happens when a record is logged while the queue is full.
+    /// 
+    /// # Returns
+    /// The same HumanLogger as `self`, but configured to write asynchronously.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, OverflowPolicy};
+    /// use log::info;
+    /// 
+    /// // Rather lose some messages than slow down the program
+    /// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_async(1024, OverflowPolicy::DropOldest).init() {
+    ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    /// }
+    /// 
+    /// info!("This message is written by another thread");
+    /// ```
+    /// 
+    /// Flushing waits until everything that was logged before has actually been written:
+    /// ```rust
+    /// use std::io::{self, Write};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter, OverflowPolicy};
+    /// use log::{warn, Level};
+    /// 
+    /// /// A writer that takes its time.
+    /// struct Slow(Arc<Mutex<Vec<u8>>>);
+    /// impl Write for Slow {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         thread::sleep(Duration::from_millis(100));
+    ///         self.0.lock().unwrap().write(buf)
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    /// }
+    /// 
+    /// let memory: Arc<Mutex<Vec<u8>>> = Arc::default();
+    /// let writer: LogWriter = LogWriter::new(Slow(memory.clone()), ColourChoice::No, [ Level::Warn ], "slow");
+    /// HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly).with_async(16, OverflowPolicy::Block).init().unwrap();
+    /// 
+    /// for i in 1..=3 {
+    ///     warn!("Record {i}");
+    /// }
+    /// assert_ne!(String::from_utf8_lossy(&memory.lock().unwrap()), "WARNING: Record 1\nWARNING: Record 2\nWARNING: Record 3\n");
+    /// log::logger().flush();
+    /// assert_eq!(String::from_utf8_lossy(&memory.lock().unwrap()), "WARNING: Record 1\nWARNING: Record 2\nWARNING: Record 3\n");
+    /// ```
+    #[inline]
+    pub fn with_async(mut self, capacity: usize, overflow: OverflowPolicy) -> Self {
+        self.asynchronous = Some((capacity, overflow));
+        self
+    }
+
+
+
+    /// Starts the writer thread and moves all writers to it.
+    /// 
+    /// Does nothing if we do not log asynchronously, or if the thread was already started.
+    fn start_background(&mut self) {
+        let (capacity, overflow): (usize, OverflowPolicy) = match self.asynchronous {
+            Some(config) if self.background.is_none() => config,
+            _                                         => return,
+        };
+        let background: Background = match Background::start(capacity, overflow) {
+            Ok(background) => background,
+            Err(err)       => {
+                eprintln!("{}: Failed to start writer thread: {} (will log synchronously instead)", style("WARNING").yellow().bold(), err);
+                return;
+            },
+        };
+
+        // Swap all writers out for ones that write to the thread instead
+        let config: RwLockReadGuard<Config> = self.config.read();
+        let unique: Vec<&SharedWriter> = config.unique_writers();
+        let writers: Vec<Box<dyn Send + Sync + Write>> = unique.iter().map(|w| match std::mem::replace(&mut w.lock().1.writer, Sink::Direct(Box::new(std::io::sink()))) {
+            Sink::Direct(writer) => writer,
+            Sink::Queued(_)      => unreachable!(),
+        }).collect();
+        for (w, queued) in unique.iter().zip(background.attach(writers)) {
+            w.lock().1.writer = Sink::Queued(queued);
+        }
+        drop(config);
+        self.background = Some(Arc::new(background));
+    }
+
+
+
+    /// Returns the writers that are currently not written to because writing to them failed.
+    /// 
+    /// Once the logger has been installed with `HumanLogger::init()`, use [`disabled_writers()`] instead.
+    /// 
+    /// # Returns
+    /// A [`WriterStatus`] for every writer that is disabled or waiting to be retried, in the order in which they were given.
+    #[inline]
+    pub fn disabled_writers(&self) -> Vec<WriterStatus> { self.config.read().disabled_writers() }
+
+
+
     /// Initializes this logger as the `log`-crate's logger.
     /// 
     /// # Errors
@@ -592,105 +1554,111 @@ impl HumanLogger {
     ///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
     /// }
     /// ```
-    pub fn init(self) -> Result<(), SetLoggerError> {
-        // Set the logger
-        let debug = self.debug;
+    #[inline]
+    pub fn init(self) -> Result<(), SetLoggerError> { self.init_with_handle().map(|_| ()) }
+
+    /// Initializes this logger as the `log`-crate's logger, returning a handle to change it at runtime.
+    /// 
+    /// # Returns
+    /// A [`LoggerHandle`] that can be used to change the debug mode, directives and writers of the installed logger. It can be cloned freely.
+    /// 
+    /// # Errors
+    /// This function may error if we failed to setup the logger. This can happen if there already was one or any other reason that `log` crashes.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use humanlog::{DebugMode, HumanLogger, LoggerHandle};
+    /// 
+    /// let handle: LoggerHandle = match HumanLogger::terminal(DebugMode::HumanFriendly).init_with_handle() {
+    ///     Ok(handle) => handle,
+    ///     Err(err)   => {
+    ///         eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+    ///         return;
+    ///     },
+    /// };
+    /// 
+    /// // Later, e.g., when receiving a signal
+    /// handle.set_mode(DebugMode::Full);
+    /// ```
+    pub fn init_with_handle(mut self) -> Result<LoggerHandle, SetLoggerError> {
+        // Start the writer thread, if any, and make sure it's drained when we exit
+        self.start_background();
+        if let Some(background) = &self.background { background.drain_at_exit(); }
+
+        // Set the logger, remembering its configuration for `disabled_writers()`
+        let handle: LoggerHandle = LoggerHandle { config: self.config.clone(), background: self.background.clone() };
         log::set_boxed_logger(Box::new(self))?;
+        let _ = INSTALLED.set(handle.config.clone());
 
-        // Set the maximum level based on the debug
-        log::set_max_level(match debug {
-            DebugMode::HumanFriendly => LevelFilter::Warn,
-            DebugMode::Debug         => LevelFilter::Debug,
-            DebugMode::Full          => LevelFilter::Trace,
-        });
+        // Set the maximum level based on the debug mode and directives
+        handle.sync_max_level();
 
         // Done
-        Ok(())
+        Ok(handle)
     }
 }
 
 impl Log for HumanLogger {
+    #[inline]
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        // Match on the level of the message to get the list of writers to write to
-        let writers: &[Arc<Mutex<(bool, InternalLogWriter)>>] = match metadata.level() {
-            Level::Error => &self.error_writers,
-            Level::Warn  => &self.warn_writers,
-            Level::Info  => &self.info_writers,
-            Level::Debug => &self.debug_writers,
-            Level::Trace => &self.trace_writers,
-        };
-
-        // Search those writers for _any_ non-enabled one
-        writers.iter().any(|w| w.lock().0)
+        // Captures want everything; otherwise, the gate knows if _any_ writer for this level allows this target
+        capture::is_capturing() || self.gate.enabled(metadata.level(), metadata.target())
     }
 
     fn log(&self, record: &log::Record) {
-        // Match on the level of the message to get the list of writers to write to
-        let writers: &[Arc<Mutex<(bool, InternalLogWriter)>>] = match record.level() {
-            Level::Error => &self.error_writers,
-            Level::Warn  => &self.warn_writers,
-            Level::Info  => &self.info_writers,
-            Level::Debug => &self.debug_writers,
-            Level::Trace => &self.trace_writers,
-        };
+        // Tests capturing records on this thread get them instead of the writers
+        let scopes: scope::Snapshot = scope::snapshot();
+        if capture::capture(record, &scopes) { return; }
 
-        // Write it to all writers who like this message
-        for w in writers {
-            let mut lock: MutexGuard<(bool, InternalLogWriter)> = w.lock();
-            let (enabled, writer): &mut (bool, InternalLogWriter) = lock.deref_mut();
+        // Let the user know first if the writer thread had to drop records
+        if let Some(background) = &self.background {
+            let dropped: u64 = background.queue().take_dropped();
+            if dropped > 0 {
+                self.log(&Record::builder().level(Level::Warn).target("humanlog").args(format_args!("Dropped {dropped} log message(s) because the writer thread could not keep up")).build());
+            }
+        }
+
+        // Write it to all writers who like this message, rendering it only once for writers that share a layout
+        let config: RwLockReadGuard<Config> = self.config.read_recursive();
+        let time: DateTime<Utc> = Utc::now();
+        let mut renders: Renders = Renders::take();
+        let mut died: bool = false;
+        for w in config.writers(record.level()) {
+            let mut lock: MutexGuard<(WriterState, InternalLogWriter)> = w.lock();
+            let (state, writer): &mut (WriterState, InternalLogWriter) = lock.deref_mut();
 
             // Skip if the writer is no longer enabled (because of an error)
-            if !*enabled { continue; }
+            if !state.is_alive() { continue; }
+            // Skip if the directives don't allow this record
+            if record.level() > config.level_for(writer, record.target()) { continue; }
+            // Skip if the writer's predicate doesn't allow this record
+            if writer.predicate.as_ref().is_some_and(|predicate| !predicate(record.metadata())) { continue; }
 
-            // Write the time, if debug logging
-            if self.debug == DebugMode::Debug {
-                log_write!(enabled, writer, "[{} ", Style::new().force_styling(writer.colour).dim().apply_to(Local::now().format("%Y-%m-%dT%H:%M:%SZ")));
-            } else if self.debug == DebugMode::Full {
-                log_write!(enabled, writer, "[{} ", Style::new().force_styling(writer.colour).dim().apply_to(Local::now().to_rfc3339()));
-            }
-            // Write the verbosity level
-            log_write!(enabled, writer, "{}", match record.level() {
-                Level::Trace => Style::new().force_styling(writer.colour).bold().apply_to("TRACE"),
-                Level::Debug => Style::new().force_styling(writer.colour).bold().blue().apply_to("DEBUG"),
-                Level::Info  => Style::new().force_styling(writer.colour).bold().green().apply_to("INFO"),
-                Level::Warn  => Style::new().force_styling(writer.colour).bold().yellow().apply_to("WARNING"),
-                Level::Error => Style::new().force_styling(writer.colour).bold().red().apply_to("ERROR"),
-            });
-            // Write the module
-            if self.debug == DebugMode::Debug {
-                let target: &str = record.target();
-                if let Some(module_path) = record.module_path() {
-                    // We only add if they actually differ
-                    if module_path != target {
-                        log_write!(enabled, writer, " {}", Style::new().force_styling(writer.colour).dim().apply_to(module_path));
-                    }
-                }
-                log_write!(enabled, writer, " {}]", Style::new().force_styling(writer.colour).bold().apply_to(target));
-            } else if self.debug == DebugMode::Full {
-                if let Some(file) = record.file() {
-                    log_write!(enabled, writer, " {}{}",
-                        Style::new().force_styling(writer.colour).dim().apply_to(file),
-                        if let Some(line) = record.line() {
-                            format!("{}", Style::new().force_styling(writer.colour).dim().apply_to(format!(":{}", line)))
-                        } else {
-                            String::new()
-                        },
-                    );
-                }
-                log_write!(enabled, writer, " {}]", Style::new().force_styling(writer.colour).bold().apply_to(record.target()));
-            }
+            // Let the writer's formatter render the record (unless another writer already did), then write it in one go (as far as the writer's error policy allows)
+            let ctx: FormatContext = FormatContext { colour: writer.colour, mode: config.debug, timestamp: writer.timestamp.as_ref().unwrap_or(&config.timestamp), multiline: config.multiline, theme: &config.theme, alignment: config.alignment, fields: writer.fields.unwrap_or(config.fields), scopes: scopes.stack_for(writer.id), time };
+            let key: Option<RenderKey> = writer.formatter.layout().map(|layout| RenderKey { layout, colour: ctx.colour, timestamp: ctx.timestamp, fields: ctx.fields, announced: ctx.scopes.announced() });
+            writer.policy.attempt(state, &writer.label, "write to", &mut writer.writer, |w| {
+                let buf: &[u8] = renders.render(key, |buf| writer.formatter.format(buf, record, &ctx))?;
+                w.write_all(buf)?;
 
-            // Now write the message
-            log_writeln!(enabled, writer, "{}{}", if self.debug == DebugMode::HumanFriendly { ": " } else { " " }, record.args());
+                // Only now that the writer has the record, it has seen its scopes
+                scopes.announce(writer.id);
+                Ok(())
+            });
+            died |= !state.is_alive();
         }
+        renders.give_back();
+
+        // Stop enabling records for writers that just died
+        if died { config.update_gate(); }
     }
 
     fn flush(&self) {
-        // Flush all the writers if they are enabled
-        log_flush!(&self.error_writers);
-        log_flush!(&self.warn_writers);
-        log_flush!(&self.info_writers);
-        log_flush!(&self.debug_writers);
-        log_flush!(&self.trace_writers);
+        // Flush all the writers if they are enabled, once each (which, for the writer thread, means waiting for it once each)
+        let config: RwLockReadGuard<Config> = self.config.read_recursive();
+        let died: bool = log_flush!(config.unique_writers());
+
+        // Stop enabling records for writers that just died
+        if died { config.update_gate(); }
     }
 }
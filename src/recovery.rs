@@ -0,0 +1,304 @@
+//  RECOVERY.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:14:40
+//  Last edited:
+//    16 Oct 2026, 15:14:40
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the [`ErrorPolicy`], which decides what happens to a
+//!   [`LogWriter`](crate::LogWriter) once writing to it fails.
+// 
+
+use std::fmt::{Debug, Formatter as FFormatter, Result as FResult};
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use console::style;
+
+use crate::background::Sink;
+
+
+/***** TYPES *****/
+/// The type of the function that opens a new writer for [`ErrorPolicy::Reopen`].
+type Opener = dyn Send + Sync + Fn() -> io::Result<Box<dyn Send + Sync + Write>>;
+
+/// The type of the function that is called by [`ErrorPolicy::Callback`].
+type Callback = dyn Send + Sync + Fn(&str, &io::Error) -> ErrorAction;
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines what to do with a [`LogWriter`](crate::LogWriter) after an [`ErrorPolicy::Callback`] has been called.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorAction {
+    /// Keep writing to the writer as if nothing happened.
+    Keep,
+    /// Never write to the writer again.
+    Disable,
+}
+
+
+
+/// Describes a [`LogWriter`](crate::LogWriter) that is currently not written to because of an error.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger};
+/// 
+/// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).init() {
+///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+/// }
+/// 
+/// // Somewhere later, e.g., in a health check
+/// for status in humanlog::disabled_writers() {
+///     println!("Writer '{}' is down: {}", status.label, status.error);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct WriterStatus {
+    /// The label of the writer.
+    pub label    : String,
+    /// The error that caused the writer to be disabled.
+    pub error    : String,
+    /// When we will attempt to write to the writer again, or [`None`] if it's disabled for good.
+    pub retry_at : Option<Instant>,
+}
+
+
+
+/// The state of a writer, as far as errors are concerned.
+pub(crate) enum WriterState {
+    /// The writer is written to as usual.
+    Enabled,
+    /// The writer is not written to until the given time, after which we try again.
+    Suspended { until: Instant, delay: Duration, error: String },
+    /// The writer is never written to again.
+    Disabled { error: String },
+}
+impl WriterState {
+    /// Returns whether the writer may still be written to, either now or in the future.
+    #[inline]
+    pub(crate) fn is_alive(&self) -> bool { !matches!(self, Self::Disabled { .. }) }
+
+    /// Returns a [`WriterStatus`] describing this state if the writer is currently not written to.
+    /// 
+    /// # Arguments
+    /// - `label`: The label of the writer in this state.
+    /// 
+    /// # Returns
+    /// A new WriterStatus, or [`None`] if the writer is enabled.
+    pub(crate) fn status(&self, label: &str) -> Option<WriterStatus> {
+        match self {
+            Self::Enabled                        => None,
+            Self::Suspended { until, error, .. } => Some(WriterStatus { label: label.into(), error: error.clone(), retry_at: Some(*until) }),
+            Self::Disabled { error }             => Some(WriterStatus { label: label.into(), error: error.clone(), retry_at: None }),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Decides what happens to a [`LogWriter`](crate::LogWriter) once writing to (or flushing) it fails.
+/// 
+/// The record that failed to be written is lost in all cases.
+/// 
+/// # Examples
+/// ```rust
+/// use std::fs::File;
+/// use std::time::Duration;
+/// use humanlog::{ColourChoice, DebugMode, ErrorPolicy, HumanLogger, LogWriter};
+/// use log::Level;
+/// 
+/// // Re-create the file if writing to it fails (e.g., because the disk was full), trying again after 1s, 2s, 4s, ..., up to once every minute
+/// let policy: ErrorPolicy = ErrorPolicy::reopen(Duration::from_secs(1), Duration::from_secs(60), || File::create("output.log"));
+/// match File::create("output.log") {
+///     Ok(handle) => {
+///         let file_logger: LogWriter = LogWriter::new(handle, ColourChoice::No, vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file").with_error_policy(policy);
+///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+///         }
+///     },
+/// 
+///     Err(err) => {
+///         eprintln!("WARNING: Failed to initialize logger: Failed to create file 'output.log': {err} (no logging enabled for this session)");
+///     },
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub enum ErrorPolicy {
+    /// Warns the user and never writes to the writer again.
+    /// 
+    /// This is the default.
+    #[default]
+    Disable,
+    /// Warns the user and skips the writer for a while, after which it is written to again. Every consecutive failure doubles the time we wait.
+    /// 
+    /// If the time to wait is too long to represent (e.g., [`Duration::MAX`]), the writer is disabled instead.
+    Retry {
+        /// The time to wait after the first failure.
+        initial : Duration,
+        /// The maximum time to wait.
+        max     : Duration,
+    },
+    /// Like [`ErrorPolicy::Retry`], but the writer is replaced by a freshly opened one before trying again (e.g., to re-create a log file that was removed).
+    Reopen {
+        /// The time to wait after the first failure.
+        initial : Duration,
+        /// The maximum time to wait.
+        max     : Duration,
+        /// Opens the new writer.
+        open    : Arc<Opener>,
+    },
+    /// Calls the given function with the label of the writer and the error, which decides what to do next. Nothing is printed.
+    /// 
+    /// Note that the function must not log anything to the writer that failed, as it is called while that writer is locked.
+    Callback(Arc<Callback>),
+}
+impl ErrorPolicy {
+    /// Constructor for the ErrorPolicy that retries with exponential backoff.
+    /// 
+    /// # Arguments
+    /// - `initial`: The time to wait after the first failure.
+    /// - `max`: The maximum time to wait, no matter how many times it failed.
+    /// 
+    /// # Returns
+    /// A new `ErrorPolicy::Retry`.
+    #[inline]
+    pub fn retry(initial: Duration, max: Duration) -> Self { Self::Retry { initial, max } }
+
+    /// Constructor for the ErrorPolicy that reopens the writer with exponential backoff.
+    /// 
+    /// # Arguments
+    /// - `initial`: The time to wait after the first failure.
+    /// - `max`: The maximum time to wait, no matter how many times it failed.
+    /// - `open`: A function that opens a new writer to replace the failed one. If it fails, that counts as another failure.
+    /// 
+    /// # Returns
+    /// A new `ErrorPolicy::Reopen`.
+    #[inline]
+    pub fn reopen<W: 'static + Send + Sync + Write>(initial: Duration, max: Duration, open: impl 'static + Send + Sync + Fn() -> io::Result<W>) -> Self {
+        Self::Reopen { initial, max, open: Arc::new(move || open().map(|writer| Box::new(writer) as Box<dyn Send + Sync + Write>)) }
+    }
+
+    /// Constructor for the ErrorPolicy that calls the given function on errors.
+    /// 
+    /// # Arguments
+    /// - `callback`: The function to call with the label of the writer and the error. Returns an [`ErrorAction`] that decides what happens to the writer.
+    /// 
+    /// # Returns
+    /// A new `ErrorPolicy::Callback`.
+    /// 
+    /// # Examples
+    /// ```rust
+    /// use std::io::ErrorKind;
+    /// use humanlog::{ErrorAction, ErrorPolicy, LogWriter};
+    /// 
+    /// // Ignore writes that would block, but give up on anything else
+    /// let writer: LogWriter = LogWriter::stdout().with_error_policy(ErrorPolicy::callback(|label, err| {
+    ///     if err.kind() == ErrorKind::WouldBlock { return ErrorAction::Keep; }
+    ///     eprintln!("Giving up on '{label}': {err}");
+    ///     ErrorAction::Disable
+    /// }));
+    /// ```
+    #[inline]
+    pub fn callback(callback: impl 'static + Send + Sync + Fn(&str, &io::Error) -> ErrorAction) -> Self { Self::Callback(Arc::new(callback)) }
+
+
+
+    /// Performs an operation on a writer, applying this policy if it fails.
+    /// 
+    /// # Arguments
+    /// - `state`: The current `WriterState` of the writer, which is updated accordingly.
+    /// - `label`: The label of the writer, for use in warnings.
+    /// - `action`: A description of the operation, for use in warnings (e.g., `write to`).
+    /// - `sink`: The writer to perform the operation on.
+    /// - `op`: The operation to perform.
+    pub(crate) fn attempt(&self, state: &mut WriterState, label: &str, action: &str, sink: &mut Sink, op: impl FnOnce(&mut Sink) -> io::Result<()>) {
+        match state {
+            WriterState::Enabled         => {},
+            WriterState::Disabled { .. } => return,
+            WriterState::Suspended { until, .. } => {
+                if Instant::now() < *until { return; }
+
+                // Open a new writer first if we're told to
+                if let Self::Reopen { open, .. } = self {
+                    match open() {
+                        Ok(writer) => sink.replace(writer),
+                        Err(err)   => {
+                            self.fail(state, label, "reopen", err);
+                            return;
+                        },
+                    }
+                }
+            },
+        }
+
+        // Run the operation
+        match op(sink) {
+            Ok(_)    => { *state = WriterState::Enabled; },
+            Err(err) => self.fail(state, label, action, err),
+        }
+    }
+
+    /// Updates the state of a writer after an operation on it failed.
+    /// 
+    /// # Arguments
+    /// - `state`: The current `WriterState` of the writer, which is updated accordingly.
+    /// - `label`: The label of the writer, for use in warnings.
+    /// - `action`: A description of the operation that failed, for use in warnings.
+    /// - `err`: The error that occurred.
+    fn fail(&self, state: &mut WriterState, label: &str, action: &str, err: io::Error) {
+        match self {
+            Self::Disable => {
+                eprintln!("{}: Failed to {} writer '{}': {} (will not attempt again)", style("WARNING").yellow().bold(), action, label, err);
+                *state = WriterState::Disabled { error: err.to_string() };
+            },
+
+            Self::Retry { initial, max } | Self::Reopen { initial, max, .. } => {
+                // Double the previous delay, if any
+                let delay: Duration = match state {
+                    WriterState::Suspended { delay, .. } => delay.saturating_mul(2).min(*max),
+                    _                                    => (*initial).min(*max),
+                };
+                match Instant::now().checked_add(delay) {
+                    Some(until) => {
+                        eprintln!("{}: Failed to {} writer '{}': {} (will attempt again in {:?})", style("WARNING").yellow().bold(), action, label, err, delay);
+                        *state = WriterState::Suspended { until, delay, error: err.to_string() };
+                    },
+                    // The delay is so long that we would never try again anyway
+                    None => {
+                        eprintln!("{}: Failed to {} writer '{}': {} (will not attempt again)", style("WARNING").yellow().bold(), action, label, err);
+                        *state = WriterState::Disabled { error: err.to_string() };
+                    },
+                }
+            },
+
+            Self::Callback(callback) => match callback(label, &err) {
+                ErrorAction::Keep    => { *state = WriterState::Enabled; },
+                ErrorAction::Disable => { *state = WriterState::Disabled { error: err.to_string() }; },
+            },
+        }
+    }
+}
+
+impl Debug for ErrorPolicy {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        match self {
+            Self::Disable                     => write!(f, "Disable"),
+            Self::Retry { initial, max }      => f.debug_struct("Retry").field("initial", initial).field("max", max).finish(),
+            Self::Reopen { initial, max, .. } => f.debug_struct("Reopen").field("initial", initial).field("max", max).finish_non_exhaustive(),
+            Self::Callback(_)                 => f.debug_tuple("Callback").finish_non_exhaustive(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//  RENDER.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:50:42
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the [`Renders`], a per-thread buffer in which a record is
+//!   rendered once for every distinct layout of the writers it goes to.
+// 
+
+use std::cell::Cell;
+use std::ops::Range;
+
+use crate::fields::PrefixFields;
+use crate::timestamp::TimestampStyle;
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The buffer of the current thread, which is kept around so its memory can be reused by the next record.
+    static RENDERS: Cell<Renders> = const { Cell::new(Renders::new()) };
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Identifies everything that decides how a record is rendered for a particular writer, besides what all writers share.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub(crate) struct RenderKey {
+    /// The name of the layout of the writer's formatter.
+    pub(crate) layout    : &'static str,
+    /// Whether the writer writes with ANSI colours.
+    pub(crate) colour    : bool,
+    /// The timestamp style of the writer. It's compared by address, which suffices because writers either share the logger's style or have their own.
+    pub(crate) timestamp : *const TimestampStyle,
+    /// The thread and process fields of the writer.
+    pub(crate) fields    : PrefixFields,
+    /// The number of scopes that earlier records already introduced on the writer.
+    pub(crate) announced : usize,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// The renders of a single record.
+/// 
+/// Use [`Renders::take()`] to borrow the buffer of the current thread, and [`Renders::give_back()`] to return it once the record is written.
+#[derive(Default)]
+pub(crate) struct Renders {
+    /// The bytes of all renders, one after another.
+    buf  : Vec<u8>,
+    /// Where in the buffer the render for every key is.
+    done : Vec<(RenderKey, Range<usize>)>,
+}
+impl Renders {
+    /// Constructor for an empty Renders.
+    #[inline]
+    const fn new() -> Self { Self { buf: Vec::new(), done: Vec::new() } }
+
+    /// Takes the buffer of the current thread.
+    /// 
+    /// If it's already taken (e.g., because formatting a record logged another one) or the thread is shutting down, a new one is returned instead.
+    #[inline]
+    pub(crate) fn take() -> Self { RENDERS.try_with(Cell::take).unwrap_or_default() }
+
+    /// Clears the renders and returns the buffer to the current thread, so that the next record can reuse it.
+    #[inline]
+    pub(crate) fn give_back(mut self) {
+        self.buf.clear();
+        self.done.clear();
+        let _ = RENDERS.try_with(|renders| renders.set(self));
+    }
+
+
+
+    /// Returns the render of the record with the given key, rendering it first if no writer with the same key rendered it yet.
+    /// 
+    /// # Arguments
+    /// - `key`: The [`RenderKey`] of the writer, or [`None`] if its render cannot be shared.
+    /// - `render`: A closure that renders the record to the given buffer.
+    /// 
+    /// # Returns
+    /// The rendered bytes.
+    /// 
+    /// # Errors
+    /// This function errors if the record had to be rendered, and the given closure failed.
+    pub(crate) fn render(&mut self, key: Option<RenderKey>, render: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> std::io::Result<&[u8]> {
+        if let Some(range) = key.and_then(|key| self.done.iter().find(|(k, _)| *k == key)).map(|(_, range)| range.clone()) {
+            return Ok(&self.buf[range]);
+        }
+
+        // Render it after any earlier renders, forgetting about it if it fails
+        let start: usize = self.buf.len();
+        if let Err(err) = render(&mut self.buf) {
+            self.buf.truncate(start);
+            return Err(err);
+        }
+        if let Some(key) = key { self.done.push((key, start..self.buf.len())); }
+        Ok(&self.buf[start..])
+    }
+}
@@ -0,0 +1,420 @@
+//  ROTATE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:09:00
+//  Last edited:
+//    16 Oct 2026, 15:09:00
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements a [`RotatingFile`], which is a file that is rotated
+//!   once it grows too large or a new day or hour starts.
+// 
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use console::style;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Appends a suffix to the given path.
+/// 
+/// # Arguments
+/// - `path`: The path to append to.
+/// - `suffix`: The suffix to append (including any separating dot).
+/// 
+/// # Returns
+/// A new [`PathBuf`] with the suffix appended to its filename.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Compresses the given file with gzip, and removes the original.
+/// 
+/// # Arguments
+/// - `path`: The path of the file to compress. The compressed file will have the same name with `.gz` appended.
+/// 
+/// # Errors
+/// This function errors if we failed to read the file, write the compressed file or remove the original.
+#[cfg(feature = "gzip")]
+fn compress(path: &Path) -> io::Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    // Copy the file through an encoder
+    let mut source: File = File::open(path)?;
+    let mut encoder: GzEncoder<File> = GzEncoder::new(File::create(with_suffix(path, ".gz"))?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+
+    // Only then remove the original
+    fs::remove_file(path)
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines at which time boundaries a [`RotatingFile`] is rotated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RotationInterval {
+    /// The file is rotated when the first record of a new hour (in local time) is written.
+    Hourly,
+    /// The file is rotated when the first record of a new day (in local time) is written.
+    Daily,
+}
+impl RotationInterval {
+    /// Returns a key that identifies the period in which the given time falls.
+    /// 
+    /// # Arguments
+    /// - `time`: The time to find the period of.
+    /// 
+    /// # Returns
+    /// A string that is equal for all times within the same period.
+    fn period(&self, time: DateTime<Local>) -> String {
+        match self {
+            Self::Hourly => time.format("%Y-%m-%dT%H").to_string(),
+            Self::Daily  => time.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Defines how rotated files are named.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RotationNaming {
+    /// Rotated files are numbered, where `.1` is the most recent one (e.g., `output.log.1`, `output.log.2`, ...).
+    #[default]
+    Numbered,
+    /// Rotated files are suffixed with the time they were rotated (e.g., `output.log.2023-03-03T18-10-13`).
+    Timestamped,
+}
+
+
+
+/// Defines when and how a [`RotatingFile`] is rotated.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{RotationInterval, RotationNaming, RotationPolicy};
+/// 
+/// // Rotate every day or when the file exceeds 10 MiB, keeping the last 7 files
+/// let policy: RotationPolicy = RotationPolicy::new()
+///     .with_max_size(10 * 1024 * 1024)
+///     .with_interval(RotationInterval::Daily)
+///     .with_keep(7)
+///     .with_naming(RotationNaming::Timestamped);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RotationPolicy {
+    /// The size (in bytes) after which the file is rotated, if any.
+    max_size : Option<u64>,
+    /// The time boundary at which the file is rotated, if any.
+    interval : Option<RotationInterval>,
+    /// The number of rotated files to keep.
+    keep     : usize,
+    /// How to name rotated files.
+    naming   : RotationNaming,
+    /// Whether to compress rotated files with gzip.
+    #[cfg(feature = "gzip")]
+    compress : bool,
+}
+impl Default for RotationPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_size : None,
+            interval : None,
+            keep     : 5,
+            naming   : RotationNaming::default(),
+            #[cfg(feature = "gzip")]
+            compress : false,
+        }
+    }
+}
+impl RotationPolicy {
+    /// Constructor for the RotationPolicy that never rotates and keeps five old files.
+    /// 
+    /// Use the `with_*()`-methods to configure when to rotate.
+    /// 
+    /// # Returns
+    /// A new RotationPolicy.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Rotates the file once it has grown to (at least) the given size.
+    /// 
+    /// Files are only rotated in between records, so the file may grow slightly larger than this size.
+    /// 
+    /// # Arguments
+    /// - `max_size`: The size (in bytes) after which the file is rotated.
+    /// 
+    /// # Returns
+    /// The same RotationPolicy as `self`, but with the new maximum size set.
+    #[inline]
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Rotates the file once a new hour or day starts.
+    /// 
+    /// # Arguments
+    /// - `interval`: The `RotationInterval` that determines the time boundary.
+    /// 
+    /// # Returns
+    /// The same RotationPolicy as `self`, but with the new interval set.
+    #[inline]
+    pub fn with_interval(mut self, interval: RotationInterval) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets how many rotated files to keep. Older ones are removed.
+    /// 
+    /// # Arguments
+    /// - `keep`: The number of rotated files to keep. If `0`, rotated files are removed immediately.
+    /// 
+    /// # Returns
+    /// The same RotationPolicy as `self`, but with the new number of files set.
+    #[inline]
+    pub fn with_keep(mut self, keep: usize) -> Self {
+        self.keep = keep;
+        self
+    }
+
+    /// Sets how rotated files are named.
+    /// 
+    /// # Arguments
+    /// - `naming`: The `RotationNaming` to use.
+    /// 
+    /// # Returns
+    /// The same RotationPolicy as `self`, but with the new naming scheme set.
+    #[inline]
+    pub fn with_naming(mut self, naming: RotationNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Sets whether to compress rotated files with gzip (adding `.gz` to their name).
+    /// 
+    /// Note that compression happens while rotating, and thus blocks the logger for the time it takes to compress the file.
+    /// 
+    /// # Arguments
+    /// - `compress`: Whether to compress rotated files or not.
+    /// 
+    /// # Returns
+    /// The same RotationPolicy as `self`, but with compression enabled or disabled.
+    #[cfg(feature = "gzip")]
+    #[inline]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A file that is rotated according to a [`RotationPolicy`].
+/// 
+/// Rotation only ever happens in between records, i.e., right before a write that follows a newline. The old file is only let go of once the new one has been opened successfully, so no record is lost if rotation fails; it simply ends up in the old file instead.
+/// 
+/// No locking is done by the file itself, as the HumanLogger already makes sure that only one thread writes to a [`LogWriter`](crate::LogWriter) at a time.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger, LogWriter, RotationPolicy};
+/// use log::Level;
+/// 
+/// // Log everything to a file that is rotated every 10 MiB
+/// match LogWriter::rotating("output.log", RotationPolicy::new().with_max_size(10 * 1024 * 1024), vec![ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "file") {
+///     Ok(file_logger) => {
+///         if let Err(err) = HumanLogger::new(vec![ LogWriter::stdout(), LogWriter::stderr(), file_logger ], DebugMode::Debug).init() {
+///             eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+///         }
+///     },
+/// 
+///     Err(err) => {
+///         eprintln!("WARNING: Failed to initialize logger: Failed to open file 'output.log': {err} (no logging enabled for this session)");
+///     },
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RotatingFile {
+    /// The path of the file we're writing to.
+    path         : PathBuf,
+    /// The handle of the file we're writing to.
+    file         : File,
+    /// The policy that determines when to rotate.
+    policy       : RotationPolicy,
+    /// The number of bytes in the current file.
+    size         : u64,
+    /// The period (see [`RotationInterval::period()`]) in which the current file was started.
+    period       : Option<String>,
+    /// Whether the last write ended a record (i.e., ended with a newline).
+    record_start : bool,
+}
+impl RotatingFile {
+    /// Opens a new RotatingFile at the given path.
+    /// 
+    /// If the file already exists, it is appended to (and it may be rotated before the first write if it's too large or too old).
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the file to write to.
+    /// - `policy`: The `RotationPolicy` that determines when and how to rotate.
+    /// 
+    /// # Returns
+    /// A new RotatingFile that writes to the given path.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to open the file or read its metadata.
+    pub fn open(path: impl Into<PathBuf>, policy: RotationPolicy) -> io::Result<Self> {
+        let path: PathBuf = path.into();
+        let file: File = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        // Use the file's metadata to find where we left off
+        let metadata: fs::Metadata = file.metadata()?;
+        let period: Option<String> = policy.interval.map(|interval| interval.period(metadata.modified().map(DateTime::<Local>::from).unwrap_or_else(|_| Local::now())));
+        Ok(Self { path, file, policy, size: metadata.len(), period, record_start: true })
+    }
+
+
+
+    /// Returns whether the file should be rotated before the next record.
+    fn should_rotate(&self) -> bool {
+        if let Some(max_size) = self.policy.max_size {
+            if self.size >= max_size { return true; }
+        }
+        if let (Some(interval), Some(period)) = (self.policy.interval, &self.period) {
+            if interval.period(Local::now()) != *period { return true; }
+        }
+        false
+    }
+
+    /// Moves the rotated files with a number up by one, removing those that exceed the policy's limit.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to rename or remove any of the files.
+    fn shift_numbered(&self) -> io::Result<()> {
+        for i in (1..=self.policy.keep.max(1)).rev() {
+            for ext in [ "", ".gz" ] {
+                let old: PathBuf = with_suffix(&self.path, &format!(".{i}{ext}"));
+                if !old.exists() { continue; }
+                if i >= self.policy.keep {
+                    fs::remove_file(&old)?;
+                } else {
+                    fs::rename(&old, with_suffix(&self.path, &format!(".{}{}", i + 1, ext)))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the oldest timestamped rotated files that exceed the policy's limit.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to read the directory or remove any of the files.
+    fn prune_timestamped(&self) -> io::Result<()> {
+        // Collect the rotated files, which all share our name as prefix
+        let (dir, name): (&Path, String) = match (self.path.parent(), self.path.file_name()) {
+            (Some(dir), Some(name)) => (if dir.as_os_str().is_empty() { Path::new(".") } else { dir }, format!("{}.", name.to_string_lossy())),
+            _                       => return Ok(()),
+        };
+        let mut rotated: Vec<PathBuf> = vec![];
+        for entry in fs::read_dir(dir)? {
+            let entry: fs::DirEntry = entry?;
+            let file_name: String = entry.file_name().to_string_lossy().into_owned();
+            if file_name.strip_prefix(&name).map(|rest| rest.starts_with(|c: char| c.is_ascii_digit())).unwrap_or(false) {
+                rotated.push(entry.path());
+            }
+        }
+
+        // Sort them from old to new, then remove all but the last few. We sort by modification time first, as the counter added to duplicate timestamps does not sort lexicographically.
+        rotated.sort_by_cached_key(|path| (fs::metadata(path).and_then(|m| m.modified()).ok(), path.clone()));
+        for path in &rotated[..rotated.len().saturating_sub(self.policy.keep)] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Rotates the file.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to move the current file or open a new one. In that case, we simply keep writing to the current file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        // Decide on the new name of the current file
+        let rotated: PathBuf = match self.policy.naming {
+            RotationNaming::Numbered => {
+                self.shift_numbered()?;
+                with_suffix(&self.path, ".1")
+            },
+            RotationNaming::Timestamped => {
+                let base: PathBuf = with_suffix(&self.path, &format!(".{}", Local::now().format("%Y-%m-%dT%H-%M-%S")));
+                let mut rotated: PathBuf = base.clone();
+                let mut i: usize = 1;
+                while rotated.exists() || with_suffix(&rotated, ".gz").exists() {
+                    rotated = with_suffix(&base, &format!("-{i}"));
+                    i += 1;
+                }
+                rotated
+            },
+        };
+
+        // Move the file and open a new one; only then do we drop the old handle
+        fs::rename(&self.path, &rotated)?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        self.period = self.policy.interval.map(|interval| interval.period(Local::now()));
+
+        // Post-process the rotated file. Failing this does not prevent logging, so we only warn.
+        #[cfg(feature = "gzip")]
+        if self.policy.compress {
+            if let Err(err) = compress(&rotated) {
+                eprintln!("{}: Failed to compress rotated log file '{}': {}", style("WARNING").yellow().bold(), rotated.display(), err);
+            }
+        }
+        let res: io::Result<()> = match self.policy.naming {
+            RotationNaming::Numbered    => if self.policy.keep == 0 { fs::remove_file(&rotated).or_else(|_| fs::remove_file(with_suffix(&rotated, ".gz"))) } else { Ok(()) },
+            RotationNaming::Timestamped => self.prune_timestamped(),
+        };
+        if let Err(err) = res {
+            eprintln!("{}: Failed to remove old rotated log files of '{}': {}", style("WARNING").yellow().bold(), self.path.display(), err);
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Rotate if this is the start of a new record and it's time to
+        if self.record_start && self.should_rotate() {
+            if let Err(err) = self.rotate() {
+                eprintln!("{}: Failed to rotate log file '{}': {} (will keep writing to the current file)", style("WARNING").yellow().bold(), self.path.display(), err);
+                // Don't try again until the file has grown by another maximum size or the next period starts
+                self.size = 0;
+                self.period = self.policy.interval.map(|interval| interval.period(Local::now()));
+            }
+        }
+
+        // Now write to the file
+        let n: usize = self.file.write(buf)?;
+        self.size += n as u64;
+        if n > 0 { self.record_start = buf[n - 1] == b'\n'; }
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
@@ -0,0 +1,271 @@
+//  SCOPE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:39:16
+//  Last edited:
+//    16 Oct 2026, 15:39:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements scoped context for log records, i.e., a per-thread stack
+//!   of [`Scope`]s (e.g., `request 4f2a` or `job=17`) that is written
+//!   with every record logged while they are alive.
+// 
+
+use std::cell::{Cell, RefCell, RefMut};
+use std::fmt::{Debug, Display, Formatter as FFormatter, Result as FResult};
+use std::rc::Rc;
+
+
+/***** CONSTANTS *****/
+thread_local! {
+    /// The innermost scope that is active on this thread.
+    static CURRENT: RefCell<Option<Rc<Frame>>> = const { RefCell::new(None) };
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the scopes that are active on the current thread.
+/// 
+/// # Returns
+/// A [`Snapshot`] from which a [`ScopeStack`] can be borrowed for every writer the record is written to.
+pub(crate) fn snapshot() -> Snapshot { Snapshot { innermost: CURRENT.try_with(|current| current.borrow().clone()).ok().flatten() } }
+
+
+
+
+
+/***** HELPERS *****/
+/// A single scope on the stack of a thread.
+struct Frame {
+    /// The text of the scope.
+    text      : String,
+    /// The scope this one was entered in, if any.
+    parent    : Option<Rc<Frame>>,
+    /// Whether the [`Scope`] of this frame was dropped while a scope entered later was still alive.
+    closed    : Cell<bool>,
+    /// The writers (see [`Snapshot::announce()`]) that a record was written to in this scope already.
+    announced : RefCell<Vec<usize>>,
+}
+
+/// The scopes that were active when a record was logged.
+pub(crate) struct Snapshot {
+    /// The innermost scope, if any.
+    innermost : Option<Rc<Frame>>,
+}
+impl Snapshot {
+    /// Borrows the scopes as a [`ScopeStack`] for the given writer.
+    /// 
+    /// # Arguments
+    /// - `writer`: An identifier of the writer that is unique for as long as it lives.
+    /// 
+    /// # Returns
+    /// A [`ScopeStack`] that can be given to formatters.
+    pub(crate) fn stack_for(&self, writer: usize) -> ScopeStack<'_> {
+        // Count the scopes that earlier records already introduced to this writer
+        let mut announced: usize = 0;
+        let mut frame: Option<&Frame> = self.innermost.as_deref();
+        while let Some(f) = frame {
+            if !f.closed.get() && f.announced.borrow().contains(&writer) { announced += 1; }
+            frame = f.parent.as_deref();
+        }
+        ScopeStack { innermost: self.innermost.as_deref(), announced }
+    }
+
+    /// Marks the scopes as introduced to the given writer.
+    /// 
+    /// This should be called once the record has actually been written to the writer (and not if that failed), so that the [`HumanFriendlyFormatter`](crate::HumanFriendlyFormatter) knows which scopes it already introduced on that writer.
+    /// 
+    /// # Arguments
+    /// - `writer`: An identifier of the writer that is unique for as long as it lives.
+    pub(crate) fn announce(&self, writer: usize) {
+        let mut frame: Option<&Frame> = self.innermost.as_deref();
+        while let Some(f) = frame {
+            if !f.closed.get() {
+                let mut writers: RefMut<Vec<usize>> = f.announced.borrow_mut();
+                if !writers.contains(&writer) { writers.push(writer); }
+            }
+            frame = f.parent.as_deref();
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Enters a new scope on the current thread.
+/// 
+/// Every record logged on this thread while the returned [`Scope`] is alive is written with its text: in `DebugMode::Debug` and `DebugMode::Full`, all active scopes are written (colon-separated) before the message, and in `DebugMode::HumanFriendly`, records are indented under a header with the text of the scope. Scopes can be nested.
+/// 
+/// # Arguments
+/// - `context`: The text of the scope, e.g., `"request 4f2a"`. Use `format_args!()` to build it without allocating twice.
+/// 
+/// # Returns
+/// A [`Scope`] that leaves the scope again when it is dropped.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{scope, DebugMode};
+/// use humanlog::capture::Capture;
+/// use log::{info, warn};
+/// 
+/// let capture: Capture = Capture::start().with_mode(DebugMode::HumanFriendly);
+/// {
+///     let _request = scope::enter(format_args!("request {}", "4f2a"));
+///     info!("Handling request");
+///     {
+///         let _job = scope::enter_kv("job", 17);
+///         warn!("Job is slow");
+///     }
+/// }
+/// info!("Done");
+/// 
+/// assert_eq!(capture.text(), "request 4f2a:\n  INFO: Handling request\n  job=17:\n    WARNING: Job is slow\nINFO: Done\n");
+/// assert_eq!(capture.records()[1].scopes, vec![ "request 4f2a".to_string(), "job=17".to_string() ]);
+/// ```
+/// 
+/// Scopes are introduced once per writer, so every writer that gets a record in a scope also gets its header:
+/// ```rust
+/// use humanlog::{scope, ColourChoice, DebugMode, HumanLogger, LogWriter};
+/// use humanlog::capture::MemoryWriter;
+/// use log::{Level, Log as _, Record};
+/// 
+/// let (errors, infos): (MemoryWriter, MemoryWriter) = (MemoryWriter::new(), MemoryWriter::new());
+/// let logger: HumanLogger = HumanLogger::new(vec![
+///     LogWriter::new(errors.clone(), ColourChoice::No, [ Level::Error, Level::Warn ], "errors"),
+///     LogWriter::new(infos.clone(), ColourChoice::No, [ Level::Info, Level::Debug, Level::Trace ], "infos"),
+/// ], DebugMode::HumanFriendly).with_directives("info".parse().unwrap());
+/// {
+///     let _request = scope::enter("request 4f2a");
+///     logger.log(&Record::builder().level(Level::Debug).args(format_args!("Not written anywhere")).build());
+///     logger.log(&Record::builder().level(Level::Warn).args(format_args!("Request is slow")).build());
+///     logger.log(&Record::builder().level(Level::Info).args(format_args!("Handling request")).build());
+///     logger.log(&Record::builder().level(Level::Info).args(format_args!("Request done")).build());
+/// }
+/// 
+/// assert_eq!(errors.contents(), "request 4f2a:\n  WARNING: Request is slow\n");
+/// assert_eq!(infos.contents(), "request 4f2a:\n  INFO: Handling request\n  INFO: Request done\n");
+/// ```
+pub fn enter(context: impl Display) -> Scope {
+    let frame: Rc<Frame> = CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let frame: Rc<Frame> = Rc::new(Frame { text: context.to_string(), parent: current.take(), closed: Cell::new(false), announced: RefCell::new(Vec::new()) });
+        *current = Some(frame.clone());
+        frame
+    });
+    Scope { frame }
+}
+
+/// Enters a new scope on the current thread that is written as a `key=value` pair.
+/// 
+/// See [`enter()`] for more information.
+/// 
+/// # Arguments
+/// - `key`: The key of the scope, e.g., `"job"`.
+/// - `value`: The value of the scope, e.g., `17`.
+/// 
+/// # Returns
+/// A [`Scope`] that leaves the scope again when it is dropped.
+#[inline]
+pub fn enter_kv(key: impl Display, value: impl Display) -> Scope { enter(format_args!("{key}={value}")) }
+
+
+
+/// A scope on the current thread, which is left again when this is dropped.
+/// 
+/// Create one with [`enter()`] or [`enter_kv()`]. Scopes are bound to the thread that entered them, and are expected to be dropped in the reverse order in which they were entered; if they're not, a dropped scope is no longer written but only removed once the scopes entered after it are dropped too.
+#[must_use = "the scope is left again when it's dropped"]
+pub struct Scope {
+    /// The frame of this scope on the stack.
+    frame : Rc<Frame>,
+}
+impl Debug for Scope {
+    #[inline]
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult { f.debug_struct("Scope").field("text", &self.frame.text).finish() }
+}
+impl Drop for Scope {
+    fn drop(&mut self) {
+        self.frame.closed.set(true);
+
+        // Pop ourselves and any scopes that were dropped before us
+        let _ = CURRENT.try_with(|current| {
+            let mut current = current.borrow_mut();
+            while let Some(frame) = current.as_ref().filter(|frame| frame.closed.get()) {
+                *current = frame.parent.clone();
+            }
+        });
+    }
+}
+
+
+
+/// The scopes that were active when a record was logged, as given to [`Formatter`](crate::Formatter)s.
+#[derive(Clone, Copy, Default)]
+pub struct ScopeStack<'a> {
+    /// The innermost scope, if any.
+    innermost : Option<&'a Frame>,
+    /// The number of scopes that earlier records already introduced on the same writer.
+    announced : usize,
+}
+impl<'a> ScopeStack<'a> {
+    /// Constructor for the ScopeStack that has no scopes.
+    /// 
+    /// # Returns
+    /// A new, empty ScopeStack.
+    #[inline]
+    pub const fn empty() -> Self { Self { innermost: None, announced: 0 } }
+
+
+
+    /// Calls the given closure for every scope, from the outermost to the innermost one.
+    /// 
+    /// # Arguments
+    /// - `f`: The closure to call with the text of every scope.
+    pub fn for_each(&self, mut f: impl FnMut(&'a str)) {
+        /// Visits the parents of a frame before the frame itself.
+        fn visit<'a>(frame: Option<&'a Frame>, f: &mut dyn FnMut(&'a str)) {
+            if let Some(frame) = frame {
+                visit(frame.parent.as_deref(), f);
+                if !frame.closed.get() { f(&frame.text); }
+            }
+        }
+        visit(self.innermost, &mut f);
+    }
+
+    /// Returns the text of every scope, from the outermost to the innermost one.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<&'a str> {
+        let mut scopes: Vec<&'a str> = Vec::with_capacity(self.depth());
+        self.for_each(|text| scopes.push(text));
+        scopes
+    }
+
+    /// Returns the number of scopes.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        let mut depth: usize = 0;
+        self.for_each(|_| depth += 1);
+        depth
+    }
+
+    /// Returns whether there are no scopes.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.depth() == 0 }
+
+    /// Returns the number of (outermost) scopes that were already active when an earlier record was written to the same writer.
+    /// 
+    /// This can be used to only introduce scopes once, like the [`HumanFriendlyFormatter`](crate::HumanFriendlyFormatter) does.
+    #[inline]
+    pub fn announced(&self) -> usize { self.announced }
+}
+impl<'a> Debug for ScopeStack<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult { f.debug_struct("ScopeStack").field("scopes", &self.to_vec()).field("announced", &self.announced).finish() }
+}
@@ -0,0 +1,563 @@
+//  SYSLOG.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:22:13
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements writing to syslog, either with the [`SyslogFormatter`] on
+//!   any writer or with a [`SyslogWriter`] that sends records to a
+//!   local or remote syslog daemon.
+// 
+
+use std::fmt::{Display, Formatter as FFormatter, Result as FResult, Write as _};
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::rc::Rc;
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use chrono::format::{Fixed, Item, Numeric, Pad};
+use log::kv::{self, Key, Value, VisitSource};
+use log::{Level, Record};
+
+use crate::fields;
+use crate::formatter::{FormatContext, Formatter};
+use crate::timestamp::{DisplayItems, DisplayRfc3339, TimestampPrecision, TimestampZone};
+
+
+/***** CONSTANTS *****/
+/// The default SD-ID under which key/value pairs are written in RFC 5424 structured data.
+/// 
+/// The number is the private enterprise number reserved for documentation (see RFC 5612).
+pub const DEFAULT_SD_ID: &str = "humanlog@32473";
+
+/// The items that write the timestamp of an RFC 3164 header (`%b %e %H:%M:%S`), which has no year nor zone and pads the day with a space.
+const RFC3164_ITEMS: [Item<'static>; 9] = [
+    Item::Fixed(Fixed::ShortMonthName), Item::Literal(" "), Item::Numeric(Numeric::Day, Pad::Space), Item::Literal(" "),
+    Item::Numeric(Numeric::Hour, Pad::Zero), Item::Literal(":"), Item::Numeric(Numeric::Minute, Pad::Zero), Item::Literal(":"), Item::Numeric(Numeric::Second, Pad::Zero),
+];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the syslog severity matching the given level.
+/// 
+/// # Arguments
+/// - `level`: The `Level` to map.
+/// 
+/// # Returns
+/// The numerical syslog severity (`3` for errors, `4` for warnings, `6` for info and `7` for debug and trace).
+#[inline]
+pub(crate) fn severity(level: Level) -> u8 {
+    match level {
+        Level::Error => 3,
+        Level::Warn  => 4,
+        Level::Info  => 6,
+        Level::Debug => 7,
+        Level::Trace => 7,
+    }
+}
+
+/// Returns the name of the executable we're running as.
+/// 
+/// # Returns
+/// The filename of the executable without extension, or [`None`] if it could not be found.
+pub(crate) fn app_name() -> Option<String> {
+    std::env::current_exe().ok().and_then(|path| path.file_stem().map(|name| name.to_string_lossy().into_owned()))
+}
+
+
+
+
+
+/***** HELPERS *****/
+/// Writes a syslog header field, which may only contain printable ASCII and is limited in length.
+/// 
+/// Other characters are replaced by underscores. Empty or missing fields are written as the `-` NILVALUE.
+struct HeaderField<'a> {
+    /// The value to write.
+    value : Option<&'a str>,
+    /// The maximum number of characters to write.
+    max   : usize,
+}
+impl<'a> Display for HeaderField<'a> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        match self.value {
+            Some(value) if !value.is_empty() => {
+                for c in value.chars().take(self.max) {
+                    f.write_char(if c.is_ascii_graphic() { c } else { '_' })?;
+                }
+                Ok(())
+            },
+            _ => f.write_char('-'),
+        }
+    }
+}
+
+/// Writes the key/value pairs attached to a [`Record`] (and, optionally, the thread that logged it) as an RFC 5424 structured data element, or the `-` NILVALUE if there are none.
+struct StructuredData<'r, 'a, 's> {
+    /// The record who's key/value pairs we write.
+    record : &'r Record<'a>,
+    /// The SD-ID of the element.
+    sd_id  : &'s str,
+    /// The thread that logged the record, if it should be written.
+    thread : Option<&'s str>,
+}
+impl<'r, 'a, 's> Display for StructuredData<'r, 'a, 's> {
+    fn fmt(&self, f: &mut FFormatter<'_>) -> FResult {
+        /// Visitor that writes every pair as an SD-PARAM.
+        struct Visitor<'f1, 'f2> {
+            f     : &'f1 mut FFormatter<'f2>,
+            sd_id : &'f1 str,
+            first : bool,
+        }
+        impl<'f1, 'f2> Visitor<'f1, 'f2> {
+            /// Writes a single SD-PARAM, opening the element if it's the first.
+            fn param(&mut self, key: &str, value: &dyn Display) -> FResult {
+                if self.first { write!(self.f, "[{}", self.sd_id)?; }
+                self.first = false;
+
+                // Names may not contain any of the characters that delimit them
+                self.f.write_char(' ')?;
+                for c in key.chars().take(32) {
+                    self.f.write_char(if c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"') { c } else { '_' })?;
+                }
+
+                // Values are quoted, escaping the characters that would end them
+                self.f.write_str("=\"")?;
+                for c in value.to_string().chars() {
+                    if matches!(c, '"' | '\\' | ']') { self.f.write_char('\\')?; }
+                    self.f.write_char(c)?;
+                }
+                self.f.write_char('"')
+            }
+        }
+        impl<'f1, 'f2, 'kvs> VisitSource<'kvs> for Visitor<'f1, 'f2> {
+            #[inline]
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> { Ok(self.param(key.as_str(), &value)?) }
+        }
+
+        let mut visitor = Visitor { f, sd_id: self.sd_id, first: true };
+        if let Some(thread) = self.thread { visitor.param("thread", &thread)?; }
+        self.record.key_values().visit(&mut visitor).map_err(|_| std::fmt::Error)?;
+        if visitor.first { visitor.f.write_char('-') } else { visitor.f.write_char(']') }
+    }
+}
+
+
+
+/// The socket with which a [`SyslogWriter`] sends messages.
+#[derive(Debug)]
+enum Socket {
+    /// A Unix datagram socket, e.g., `/dev/log`.
+    #[cfg(unix)]
+    Unix(UnixDatagram),
+    /// A connected UDP socket.
+    Udp(UdpSocket),
+    /// A TCP stream, on which messages are framed by octet counting (RFC 6587).
+    Tcp(TcpStream),
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines the syslog facility, which describes the kind of program that logs a message.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SyslogFacility {
+    /// Kernel messages.
+    Kern,
+    /// Generic user-level messages.
+    /// 
+    /// This is the default.
+    #[default]
+    User,
+    /// The mail system.
+    Mail,
+    /// System daemons.
+    Daemon,
+    /// Security and authorization messages.
+    Auth,
+    /// Messages generated by the syslog daemon itself.
+    Syslog,
+    /// The line printer subsystem.
+    Lpr,
+    /// The network news subsystem.
+    News,
+    /// The UUCP subsystem.
+    Uucp,
+    /// The clock daemon.
+    Cron,
+    /// Private security and authorization messages.
+    AuthPriv,
+    /// The FTP daemon.
+    Ftp,
+    /// Reserved for local use.
+    Local0,
+    /// Reserved for local use.
+    Local1,
+    /// Reserved for local use.
+    Local2,
+    /// Reserved for local use.
+    Local3,
+    /// Reserved for local use.
+    Local4,
+    /// Reserved for local use.
+    Local5,
+    /// Reserved for local use.
+    Local6,
+    /// Reserved for local use.
+    Local7,
+}
+impl SyslogFacility {
+    /// Returns the numerical code of this facility, as used in the PRI of syslog messages.
+    #[inline]
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Kern     => 0,
+            Self::User     => 1,
+            Self::Mail     => 2,
+            Self::Daemon   => 3,
+            Self::Auth     => 4,
+            Self::Syslog   => 5,
+            Self::Lpr      => 6,
+            Self::News     => 7,
+            Self::Uucp     => 8,
+            Self::Cron     => 9,
+            Self::AuthPriv => 10,
+            Self::Ftp      => 11,
+            Self::Local0   => 16,
+            Self::Local1   => 17,
+            Self::Local2   => 18,
+            Self::Local3   => 19,
+            Self::Local4   => 20,
+            Self::Local5   => 21,
+            Self::Local6   => 22,
+            Self::Local7   => 23,
+        }
+    }
+}
+
+/// Defines the framing of syslog messages.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SyslogFormat {
+    /// The modern format of [RFC 5424](https://www.rfc-editor.org/rfc/rfc5424), which has precise timestamps and carries key/value pairs as structured data.
+    /// 
+    /// This is the default.
+    #[default]
+    Rfc5424,
+    /// The legacy BSD format of [RFC 3164](https://www.rfc-editor.org/rfc/rfc3164), for daemons that do not understand RFC 5424. Key/value pairs are appended to the message.
+    Rfc3164,
+}
+
+
+
+/// Defines where a [`SyslogWriter`] sends its messages to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SyslogTransport {
+    /// Sends messages to a Unix datagram socket at the given path.
+    /// 
+    /// The default is the local syslog daemon at `/dev/log`.
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// Sends messages as UDP datagrams to the given address (e.g., `logs.example.com:514`).
+    Udp(String),
+    /// Sends messages over a TCP connection to the given address (e.g., `logs.example.com:601`), using octet-counting framing (RFC 6587).
+    Tcp(String),
+}
+#[cfg(unix)]
+impl Default for SyslogTransport {
+    #[inline]
+    fn default() -> Self { Self::Unix("/dev/log".into()) }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A [`Formatter`] that writes records as syslog messages.
+/// 
+/// Levels are mapped to the syslog severities `err` (3), `warning` (4), `info` (6) and `debug` (7), where trace records are also written as `debug`.
+/// 
+/// In the RFC 5424 format, the target of the record is written as the MSGID and its key/value pairs as structured data. The timestamp is written in the zone of the logger's `TimestampStyle`, or as `-` if timestamps are disabled. The RFC 3164 format always has a timestamp, and appends key/value pairs to the message. Colour is never applied.
+/// 
+/// Every message ends with a newline, which a [`SyslogWriter`] strips again.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, FormatContext, Formatter as _, TimestampStyle};
+/// use humanlog::syslog::{SyslogFacility, SyslogFormat, SyslogFormatter};
+/// use log::{Level, Record};
+/// 
+/// let formatter: SyslogFormatter = SyslogFormatter::new().with_facility(SyslogFacility::Local0).with_hostname("tatooine").with_app_name("example").with_procid("42");
+/// let record: Record = Record::builder().level(Level::Warn).target("kenobi").key_values(&[("answer", 42)]).args(format_args!("Hello there!")).build();
+/// let timestamp: TimestampStyle = TimestampStyle::none();
+/// 
+/// let mut buf: Vec<u8> = Vec::new();
+/// formatter.format(&mut buf, &record, &FormatContext::new(false, DebugMode::Debug).with_timestamp(&timestamp)).unwrap();
+/// assert_eq!(String::from_utf8(buf).unwrap(), "<132>1 - tatooine example 42 kenobi [humanlog@32473 answer=\"42\"] Hello there!\n");
+/// 
+/// let mut buf: Vec<u8> = Vec::new();
+/// formatter.with_format(SyslogFormat::Rfc3164).format(&mut buf, &record, &FormatContext::new(false, DebugMode::Debug).with_timestamp(&timestamp)).unwrap();
+/// assert!(String::from_utf8(buf).unwrap().ends_with(" tatooine example[42]: Hello there! answer=42\n"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SyslogFormatter {
+    /// The facility to log as.
+    facility : SyslogFacility,
+    /// The framing of messages.
+    format   : SyslogFormat,
+    /// The name of the machine to log as.
+    hostname : Option<String>,
+    /// The name of the program to log as.
+    app_name : Option<String>,
+    /// The process ID to log as.
+    procid   : Option<String>,
+    /// The SD-ID under which key/value pairs are written.
+    sd_id    : String,
+}
+impl Default for SyslogFormatter {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl SyslogFormatter {
+    /// Constructor for the SyslogFormatter.
+    /// 
+    /// It writes RFC 5424 messages for the `user` facility, with the hostname, executable name and process ID of the current process.
+    /// 
+    /// # Returns
+    /// A new SyslogFormatter.
+    pub fn new() -> Self {
+        Self {
+            facility : SyslogFacility::default(),
+            format   : SyslogFormat::default(),
+            hostname : fields::hostname().map(String::from),
+            app_name : app_name(),
+            procid   : Some(std::process::id().to_string()),
+            sd_id    : DEFAULT_SD_ID.into(),
+        }
+    }
+
+    /// Changes the facility that messages are logged as.
+    /// 
+    /// # Arguments
+    /// - `facility`: The new `SyslogFacility` to log as.
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new facility set.
+    #[inline]
+    pub fn with_facility(mut self, facility: SyslogFacility) -> Self { self.facility = facility; self }
+
+    /// Changes the framing of messages.
+    /// 
+    /// # Arguments
+    /// - `format`: The new `SyslogFormat` to write messages in.
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new format set.
+    #[inline]
+    pub fn with_format(mut self, format: SyslogFormat) -> Self { self.format = format; self }
+
+    /// Changes the hostname that messages are logged as.
+    /// 
+    /// # Arguments
+    /// - `hostname`: The new hostname to log as.
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new hostname set.
+    #[inline]
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self { self.hostname = Some(hostname.into()); self }
+
+    /// Changes the application name that messages are logged as.
+    /// 
+    /// # Arguments
+    /// - `app_name`: The new application name to log as (the TAG in RFC 3164).
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new application name set.
+    #[inline]
+    pub fn with_app_name(mut self, app_name: impl Into<String>) -> Self { self.app_name = Some(app_name.into()); self }
+
+    /// Changes the process ID that messages are logged as.
+    /// 
+    /// # Arguments
+    /// - `procid`: The new process ID to log as. Doesn't have to be a number; RFC 5424 allows any identifier of the process (e.g., a worker name).
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new process ID set.
+    #[inline]
+    pub fn with_procid(mut self, procid: impl Into<String>) -> Self { self.procid = Some(procid.into()); self }
+
+    /// Changes the SD-ID under which key/value pairs are written in RFC 5424 structured data.
+    /// 
+    /// # Arguments
+    /// - `sd_id`: The new SD-ID, which should be of the form `name@<private enterprise number>`. Defaults to [`DEFAULT_SD_ID`].
+    /// 
+    /// # Returns
+    /// The same SyslogFormatter as `self`, but with the new SD-ID set.
+    #[inline]
+    pub fn with_sd_id(mut self, sd_id: impl Into<String>) -> Self { self.sd_id = sd_id.into(); self }
+}
+impl Formatter for SyslogFormatter {
+    fn format(&self, writer: &mut dyn Write, record: &Record, ctx: &FormatContext) -> io::Result<()> {
+        let pri: u8 = self.facility.code() * 8 + severity(record.level());
+        let thread: Option<Rc<str>> = if ctx.fields.thread() { Some(fields::thread()) } else { None };
+        match self.format {
+            SyslogFormat::Rfc5424 => {
+                write!(writer, "<{pri}>1 ")?;
+                if ctx.timestamp.is_enabled() {
+                    write!(writer, "{}", DisplayRfc3339 { time: ctx.timestamp.zone.at(ctx.time), precision: TimestampPrecision::Micros, use_z: ctx.timestamp.zone == TimestampZone::Utc })?;
+                } else {
+                    writer.write_all(b"-")?;
+                }
+                writeln!(writer, " {} {} {} {} {} {}",
+                    HeaderField { value: self.hostname.as_deref(), max: 255 },
+                    HeaderField { value: self.app_name.as_deref(), max: 48 },
+                    HeaderField { value: self.procid.as_deref(), max: 128 },
+                    HeaderField { value: Some(record.target()), max: 32 },
+                    StructuredData { record, sd_id: &self.sd_id, thread: thread.as_deref() },
+                    record.args(),
+                )
+            },
+
+            SyslogFormat::Rfc3164 => {
+                write!(writer, "<{pri}>")?;
+                write!(writer, "{}", DisplayItems { time: ctx.timestamp.zone.at(ctx.time).naive_local(), items: &RFC3164_ITEMS })?;
+                write!(writer, " {} {}", HeaderField { value: self.hostname.as_deref(), max: 255 }, HeaderField { value: self.app_name.as_deref(), max: 32 })?;
+                if let Some(procid) = self.procid.as_deref().filter(|procid| !procid.is_empty()) {
+                    write!(writer, "[{}]", HeaderField { value: Some(procid), max: 128 })?;
+                }
+                write!(writer, ": {}", record.args())?;
+                if let Some(thread) = thread {
+                    write!(writer, " thread={thread}")?;
+                }
+
+                /// Visitor that appends every pair to the message.
+                struct Visitor<'w> {
+                    writer : &'w mut dyn Write,
+                }
+                impl<'w, 'kvs> VisitSource<'kvs> for Visitor<'w> {
+                    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                        Ok(write!(self.writer, " {key}={value}")?)
+                    }
+                }
+                record.key_values().visit(&mut Visitor { writer }).map_err(|err| io::Error::other(err.to_string()))?;
+                writeln!(writer)
+            },
+        }
+    }
+}
+
+
+
+/// A writer that sends every write as one syslog message to a local or remote syslog daemon.
+/// 
+/// This relies on the HumanLogger writing every record in a single call, which it always does. A single trailing newline is stripped from every message. Use it with a [`SyslogFormatter`] to get valid syslog messages, e.g., through [`LogWriter::syslog()`](crate::LogWriter::syslog()).
+/// 
+/// # Examples
+/// ```rust
+/// # #[cfg(unix)] {
+/// use std::os::unix::net::UnixDatagram;
+/// use humanlog::{DebugMode, HumanLogger, LogWriter};
+/// use humanlog::syslog::{SyslogFormatter, SyslogTransport};
+/// use log::{Level, Log as _, Record};
+/// 
+/// // Pretend to be the syslog daemon
+/// let path = std::env::temp_dir().join(format!("humanlog-syslog-{}.sock", std::process::id()));
+/// let _ = std::fs::remove_file(&path);
+/// let daemon: UnixDatagram = UnixDatagram::bind(&path).unwrap();
+/// 
+/// // Use the logger directly instead of installing it, so it is private to this test
+/// let writer: LogWriter = LogWriter::syslog(SyslogTransport::Unix(path.clone()), SyslogFormatter::new().with_app_name("example").with_procid("42"), vec![ Level::Error, Level::Warn, Level::Info ], "syslog").unwrap();
+/// let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::Debug);
+/// logger.log(&Record::builder().level(Level::Info).target("kenobi").args(format_args!("Hello there!")).build());
+/// 
+/// let mut buf: [u8; 1024] = [0; 1024];
+/// let n: usize = daemon.recv(&mut buf).unwrap();
+/// let message: &str = std::str::from_utf8(&buf[..n]).unwrap();
+/// assert!(message.starts_with("<14>1 "));
+/// assert!(message.ends_with(" example 42 kenobi - Hello there!"));
+/// std::fs::remove_file(&path).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SyslogWriter {
+    /// The socket to send messages with.
+    socket : Socket,
+}
+impl SyslogWriter {
+    /// Constructor for the SyslogWriter that connects to the given syslog daemon.
+    /// 
+    /// # Arguments
+    /// - `transport`: The `SyslogTransport` that determines where to send messages to.
+    /// 
+    /// # Returns
+    /// A new SyslogWriter that is connected to the daemon.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to resolve the address or connect to the daemon.
+    pub fn connect(transport: &SyslogTransport) -> io::Result<Self> {
+        let socket: Socket = match transport {
+            #[cfg(unix)]
+            SyslogTransport::Unix(path) => {
+                let socket: UnixDatagram = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                Socket::Unix(socket)
+            },
+
+            SyslogTransport::Udp(addr) => {
+                // Bind to the same address family as the daemon
+                let mut last_err: io::Error = io::Error::new(io::ErrorKind::InvalidInput, format!("Address '{addr}' did not resolve to anything"));
+                let mut socket: Option<UdpSocket> = None;
+                for addr in addr.to_socket_addrs()? {
+                    let local: &str = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+                    match UdpSocket::bind(local).and_then(|socket| socket.connect(addr).map(|_| socket)) {
+                        Ok(s)    => { socket = Some(s); break; },
+                        Err(err) => { last_err = err; },
+                    }
+                }
+                Socket::Udp(socket.ok_or(last_err)?)
+            },
+
+            SyslogTransport::Tcp(addr) => Socket::Tcp(TcpStream::connect(addr.as_str())?),
+        };
+        Ok(Self { socket })
+    }
+}
+impl Write for SyslogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let message: &[u8] = buf.strip_suffix(b"\n").unwrap_or(buf);
+        match &mut self.socket {
+            #[cfg(unix)]
+            Socket::Unix(socket) => { socket.send(message)?; },
+            Socket::Udp(socket)  => { socket.send(message)?; },
+            Socket::Tcp(stream)  => {
+                // Prefix with the length, and send it in one go to avoid interleaving partial messages
+                let mut framed: Vec<u8> = Vec::with_capacity(message.len() + 8);
+                write!(framed, "{} ", message.len())?;
+                framed.extend_from_slice(message);
+                stream.write_all(&framed)?;
+            },
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.socket {
+            Socket::Tcp(stream) => stream.flush(),
+            _                   => Ok(()),
+        }
+    }
+}
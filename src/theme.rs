@@ -0,0 +1,379 @@
+//  THEME.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:32:11
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the [`Theme`], which decides the labels and colours with
+//!   which the parts of a log record are written.
+// 
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use log::Level;
+
+// Re-export the styling types, so users don't have to depend on `console` themselves
+pub use console::{Color, Style};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the index of the given level in the per-level arrays of a [`Theme`].
+#[inline]
+fn index(level: Level) -> usize {
+    match level {
+        Level::Error => 0,
+        Level::Warn  => 1,
+        Level::Info  => 2,
+        Level::Debug => 3,
+        Level::Trace => 4,
+    }
+}
+
+/// Applies the style of the given [`Painter`] to a value, but only if we write with colours.
+/// 
+/// # Arguments
+/// - `painter`: The `Painter` with the style to apply.
+/// - `colour`: Whether to write with ANSI colours.
+/// - `value`: The value to style.
+/// 
+/// # Returns
+/// A [`Painted`] that writes the value in the given style.
+#[inline]
+pub(crate) fn paint<D: Display>(painter: &Painter, colour: bool, value: D) -> Painted<'_, D> { Painted { painter, colour, value } }
+
+
+
+
+
+/***** AUXILLARY *****/
+/// A [`Style`] together with the ANSI codes it writes around a value.
+/// 
+/// `console` works these codes out (and clones the style) every time something is styled, so we do it once when the [`Theme`] is built instead.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Painter {
+    /// The style itself, as given by the user.
+    style  : Style,
+    /// The codes that start the style.
+    prefix : String,
+    /// The codes that reset the style again, which are empty if the style doesn't change anything.
+    suffix : String,
+}
+impl From<Style> for Painter {
+    fn from(style: Style) -> Self {
+        // Let `console` write the codes around a marker that never occurs in them, and split them off
+        let styled: String = style.clone().force_styling(true).apply_to('\0').to_string();
+        let (prefix, suffix): (&str, &str) = styled.split_once('\0').unwrap_or(("", ""));
+        Self { prefix: prefix.into(), suffix: suffix.into(), style }
+    }
+}
+
+/// Writes a value in the style of a [`Painter`], as returned by [`paint()`].
+#[derive(Debug)]
+pub(crate) struct Painted<'p, D> {
+    /// The painter with the style to write the value in.
+    painter : &'p Painter,
+    /// Whether to write with ANSI colours.
+    colour  : bool,
+    /// The value to write.
+    value   : D,
+}
+impl<D: Display> Display for Painted<'_, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        if self.colour { f.write_str(&self.painter.prefix)?; }
+        self.value.fmt(f)?;
+        if self.colour { f.write_str(&self.painter.suffix)?; }
+        Ok(())
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Decides the labels and colours with which the parts of a log record are written.
+/// 
+/// Styles are only applied if the [`LogWriter`](crate::LogWriter) writes with colours; otherwise, only the labels matter.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger, Theme};
+/// use humanlog::theme::Style;
+/// use log::Level;
+/// 
+/// // Start from one of the presets and change what you like
+/// let theme: Theme = Theme::fixed_width().with_label(Level::Error, "FAIL ").with_level_style(Level::Error, Style::new().magenta().bold());
+/// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_theme(theme).init() {
+///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// The labels of the levels, from `Level::Error` to `Level::Trace`.
+    labels    : [Cow<'static, str>; 5],
+    /// The styles of the level labels, from `Level::Error` to `Level::Trace`.
+    levels    : [Painter; 5],
+    /// The style of timestamps.
+    timestamp : Painter,
+    /// The style of targets.
+    target    : Painter,
+    /// The style of module paths.
+    module    : Painter,
+    /// The style of `file:line` locations.
+    location  : Painter,
+    /// The style of the thread, process ID and hostname fields.
+    fields    : Painter,
+    /// The style of scopes.
+    scope     : Painter,
+}
+impl Default for Theme {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl Theme {
+    /// Constructor for the Theme that writes records in the default colours.
+    /// 
+    /// This is the default.
+    /// 
+    /// # Returns
+    /// A new Theme with bold `ERROR` (red), `WARNING` (yellow), `INFO` (green), `DEBUG` (blue) and `TRACE` (uncoloured) labels, a dimmed timestamp, module path, location and thread/process fields, and a bold target and scopes.
+    pub fn new() -> Self {
+        Self {
+            labels    : [ "ERROR".into(), "WARNING".into(), "INFO".into(), "DEBUG".into(), "TRACE".into() ],
+            levels    : [ Style::new().bold().red().into(), Style::new().bold().yellow().into(), Style::new().bold().green().into(), Style::new().bold().blue().into(), Style::new().bold().into() ],
+            timestamp : Style::new().dim().into(),
+            target    : Style::new().bold().into(),
+            module    : Style::new().dim().into(),
+            location  : Style::new().dim().into(),
+            fields    : Style::new().dim().into(),
+            scope     : Style::new().bold().into(),
+        }
+    }
+
+    /// Constructor for the Theme that writes records with high contrast, e.g., for bright terminals or users with low vision.
+    /// 
+    /// # Returns
+    /// A new Theme with the default labels on solid backgrounds, and nothing dimmed.
+    pub fn high_contrast() -> Self {
+        Self {
+            labels    : [ "ERROR".into(), "WARNING".into(), "INFO".into(), "DEBUG".into(), "TRACE".into() ],
+            levels    : [
+                Style::new().bold().white().bright().on_red().into(),
+                Style::new().bold().black().on_yellow().into(),
+                Style::new().bold().black().on_green().into(),
+                Style::new().bold().white().bright().on_blue().into(),
+                Style::new().bold().black().on_white().into(),
+            ],
+            timestamp : Style::new().into(),
+            target    : Style::new().bold().underlined().into(),
+            module    : Style::new().into(),
+            location  : Style::new().into(),
+            fields    : Style::new().into(),
+            scope     : Style::new().bold().underlined().into(),
+        }
+    }
+
+    /// Constructor for the Theme that writes records without any colours, only using bold and dimmed text.
+    /// 
+    /// # Returns
+    /// A new Theme with the default labels, target and scopes in bold, and a dimmed timestamp, module path, location and thread/process fields.
+    pub fn monochrome() -> Self {
+        Self {
+            labels    : [ "ERROR".into(), "WARNING".into(), "INFO".into(), "DEBUG".into(), "TRACE".into() ],
+            levels    : [ Style::new().bold().into(), Style::new().bold().into(), Style::new().bold().into(), Style::new().bold().into(), Style::new().bold().into() ],
+            timestamp : Style::new().dim().into(),
+            target    : Style::new().bold().into(),
+            module    : Style::new().dim().into(),
+            location  : Style::new().dim().into(),
+            fields    : Style::new().dim().into(),
+            scope     : Style::new().bold().into(),
+        }
+    }
+
+    /// Constructor for the Theme that writes all labels with the same width, so the messages line up in columns.
+    /// 
+    /// # Returns
+    /// A new Theme with the default colours, but with `ERROR`, `WARN `, `INFO `, `DEBUG` and `TRACE` as labels.
+    pub fn fixed_width() -> Self {
+        Self { labels: [ "ERROR".into(), "WARN ".into(), "INFO ".into(), "DEBUG".into(), "TRACE".into() ], ..Self::new() }
+    }
+
+
+
+    /// Changes the label of the given level.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` to change the label of.
+    /// - `label`: The new label to write for that level.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new label set.
+    #[inline]
+    pub fn with_label(mut self, level: Level, label: impl Into<Cow<'static, str>>) -> Self {
+        self.labels[index(level)] = label.into();
+        self
+    }
+
+    /// Changes the style of the label of the given level.
+    /// 
+    /// # Arguments
+    /// - `level`: The `Level` to change the style of.
+    /// - `style`: The new `Style` to write that level's label in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new level style set.
+    #[inline]
+    pub fn with_level_style(mut self, level: Level, style: Style) -> Self {
+        self.levels[index(level)] = style.into();
+        self
+    }
+
+    /// Changes the style of timestamps.
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new timestamp style set.
+    #[inline]
+    pub fn with_timestamp_style(mut self, style: Style) -> Self {
+        self.timestamp = style.into();
+        self
+    }
+
+    /// Changes the style of targets.
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write targets in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new target style set.
+    #[inline]
+    pub fn with_target_style(mut self, style: Style) -> Self {
+        self.target = style.into();
+        self
+    }
+
+    /// Changes the style of module paths.
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write module paths in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new module path style set.
+    #[inline]
+    pub fn with_module_style(mut self, style: Style) -> Self {
+        self.module = style.into();
+        self
+    }
+
+    /// Changes the style of `file:line` locations.
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write locations in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new location style set.
+    #[inline]
+    pub fn with_location_style(mut self, style: Style) -> Self {
+        self.location = style.into();
+        self
+    }
+
+    /// Changes the style of the thread, process ID and hostname fields (see [`PrefixFields`](crate::PrefixFields)).
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write these fields in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new fields style set.
+    #[inline]
+    pub fn with_fields_style(mut self, style: Style) -> Self {
+        self.fields = style.into();
+        self
+    }
+
+    /// Changes the style of scopes (see [`scope::enter()`](crate::scope::enter())).
+    /// 
+    /// # Arguments
+    /// - `style`: The new `Style` to write scopes in.
+    /// 
+    /// # Returns
+    /// The same Theme as `self`, but with the new scope style set.
+    #[inline]
+    pub fn with_scope_style(mut self, style: Style) -> Self {
+        self.scope = style.into();
+        self
+    }
+
+
+
+    /// Returns the label of the given level.
+    #[inline]
+    pub fn label(&self, level: Level) -> &str { &self.labels[index(level)] }
+
+    /// Returns the style of the label of the given level.
+    #[inline]
+    pub fn level_style(&self, level: Level) -> &Style { &self.levels[index(level)].style }
+
+    /// Returns the style of timestamps.
+    #[inline]
+    pub fn timestamp_style(&self) -> &Style { &self.timestamp.style }
+
+    /// Returns the style of targets.
+    #[inline]
+    pub fn target_style(&self) -> &Style { &self.target.style }
+
+    /// Returns the style of module paths.
+    #[inline]
+    pub fn module_style(&self) -> &Style { &self.module.style }
+
+    /// Returns the style of `file:line` locations.
+    #[inline]
+    pub fn location_style(&self) -> &Style { &self.location.style }
+
+    /// Returns the style of the thread, process ID and hostname fields.
+    #[inline]
+    pub fn fields_style(&self) -> &Style { &self.fields.style }
+
+    /// Returns the style of scopes.
+    #[inline]
+    pub fn scope_style(&self) -> &Style { &self.scope.style }
+
+
+
+    /// Returns the painter of the label of the given level.
+    #[inline]
+    pub(crate) fn level_painter(&self, level: Level) -> &Painter { &self.levels[index(level)] }
+
+    /// Returns the painter of timestamps.
+    #[inline]
+    pub(crate) fn timestamp_painter(&self) -> &Painter { &self.timestamp }
+
+    /// Returns the painter of targets.
+    #[inline]
+    pub(crate) fn target_painter(&self) -> &Painter { &self.target }
+
+    /// Returns the painter of module paths.
+    #[inline]
+    pub(crate) fn module_painter(&self) -> &Painter { &self.module }
+
+    /// Returns the painter of `file:line` locations.
+    #[inline]
+    pub(crate) fn location_painter(&self) -> &Painter { &self.location }
+
+    /// Returns the painter of the thread, process ID and hostname fields.
+    #[inline]
+    pub(crate) fn fields_painter(&self) -> &Painter { &self.fields }
+
+    /// Returns the painter of scopes.
+    #[inline]
+    pub(crate) fn scope_painter(&self) -> &Painter { &self.scope }
+}
+
@@ -0,0 +1,363 @@
+//  TIMESTAMP.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:05:49
+//  Last edited:
+//    16 Oct 2026, 15:50:42
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the [`TimestampStyle`], which decides how the time of a log
+//!   record is written.
+// 
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use chrono::format::{Fixed, Item, Numeric, Pad, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Offset as _, SecondsFormat, Utc};
+
+use crate::DebugMode;
+
+
+/***** CONSTANTS *****/
+/// The moment from which [`TimestampFormat::Uptime`] counts.
+static START: OnceLock<Instant> = OnceLock::new();
+
+/// The items that write the date and time of an RFC 3339 timestamp, without its subseconds and offset.
+const RFC3339_ITEMS: [Item<'static>; 11] = [
+    Item::Numeric(Numeric::Year, Pad::Zero), Item::Literal("-"), Item::Numeric(Numeric::Month, Pad::Zero), Item::Literal("-"), Item::Numeric(Numeric::Day, Pad::Zero),
+    Item::Literal("T"),
+    Item::Numeric(Numeric::Hour, Pad::Zero), Item::Literal(":"), Item::Numeric(Numeric::Minute, Pad::Zero), Item::Literal(":"), Item::Numeric(Numeric::Second, Pad::Zero),
+];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Marks the moment from which [`TimestampFormat::Uptime`] counts, unless it was marked before.
+#[inline]
+pub(crate) fn mark_start() { START.get_or_init(Instant::now); }
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines errors that occur when parsing a [`TimestampStyle`].
+#[derive(Debug)]
+pub struct TimestampStyleParseError {
+    /// The raw value that we failed to parse.
+    raw : String,
+}
+impl Display for TimestampStyleParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "Unknown timestamp style '{}' (expected one of 'local', 'utc', 'none', 'unix', 'unix-millis', 'uptime' or a UTC offset like '+02:00')", self.raw)
+    }
+}
+impl Error for TimestampStyleParseError {}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Defines the timezone in which timestamps are written.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TimestampZone {
+    /// Timestamps are written in the local timezone of the machine.
+    #[default]
+    Local,
+    /// Timestamps are written in UTC.
+    Utc,
+    /// Timestamps are written with the given offset from UTC.
+    Fixed(FixedOffset),
+}
+impl TimestampZone {
+    /// Returns the current time in this zone.
+    /// 
+    /// # Returns
+    /// A [`DateTime`] that carries the offset of this zone (at this moment, for `TimestampZone::Local`).
+    #[inline]
+    pub fn now(&self) -> DateTime<FixedOffset> { self.at(Utc::now()) }
+
+    /// Returns the given time in this zone.
+    /// 
+    /// # Arguments
+    /// - `time`: The moment to convert, e.g., the time at which a record was logged.
+    /// 
+    /// # Returns
+    /// A [`DateTime`] that carries the offset of this zone (at that moment, for `TimestampZone::Local`).
+    #[inline]
+    pub fn at(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local         => time.with_timezone(&Local).into(),
+            Self::Utc           => time.into(),
+            Self::Fixed(offset) => time.with_timezone(offset),
+        }
+    }
+}
+
+/// Defines the number of subsecond digits written in a timestamp.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TimestampPrecision {
+    /// Whole seconds only.
+    Seconds,
+    /// Three digits.
+    Millis,
+    /// Six digits.
+    Micros,
+    /// Nine digits.
+    Nanos,
+}
+impl TimestampPrecision {
+    /// Returns the number of subsecond digits written with this precision.
+    #[inline]
+    pub fn digits(&self) -> usize {
+        match self {
+            Self::Seconds => 0,
+            Self::Millis  => 3,
+            Self::Micros  => 6,
+            Self::Nanos   => 9,
+        }
+    }
+}
+impl From<TimestampPrecision> for SecondsFormat {
+    #[inline]
+    fn from(value: TimestampPrecision) -> Self {
+        match value {
+            TimestampPrecision::Seconds => Self::Secs,
+            TimestampPrecision::Millis  => Self::Millis,
+            TimestampPrecision::Micros  => Self::Micros,
+            TimestampPrecision::Nanos   => Self::Nanos,
+        }
+    }
+}
+
+/// Defines the format in which timestamps are written.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TimestampFormat {
+    /// Uses the format that matches the `DebugMode`, i.e., RFC 3339 with seconds precision for `DebugMode::Debug` and with nanoseconds precision for `DebugMode::Full`. `DebugMode::HumanFriendly` never writes timestamps.
+    #[default]
+    Auto,
+    /// [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) (e.g., `2023-03-03T18:10:13.853+01:00`) with the given subsecond precision, regardless of the `DebugMode`.
+    Rfc3339(TimestampPrecision),
+    /// A custom [`strftime`-like format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) (e.g., `%H:%M:%S%.3f`). Invalid formats are written as `<invalid timestamp format>`.
+    Custom(String),
+    /// The number of whole seconds since the Unix epoch (e.g., `1677863413`). Ignores the zone.
+    UnixSeconds,
+    /// The number of milliseconds since the Unix epoch (e.g., `1677863413853`). Ignores the zone.
+    UnixMillis,
+    /// The time since the first HumanLogger was created, in seconds with the given subsecond precision (e.g., `12.345`). Ignores the zone.
+    Uptime(TimestampPrecision),
+    /// Timestamps are not written at all.
+    None,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Decides how the time of a log record is written.
+/// 
+/// Timestamps always carry the correct offset, i.e., local times are written with the local offset (e.g., `+01:00`) and UTC times with `Z`.
+/// 
+/// # Examples
+/// ```rust
+/// use humanlog::{DebugMode, HumanLogger, TimestampStyle};
+/// 
+/// // Write timestamps in UTC instead of in local time
+/// if let Err(err) = HumanLogger::terminal(DebugMode::Debug).with_timestamp(TimestampStyle::utc()).init() {
+///     eprintln!("WARNING: Failed to initialize logger: {err} (no logging enabled for this session)");
+/// }
+/// ```
+/// 
+/// ```rust
+/// use chrono::FixedOffset;
+/// use humanlog::{TimestampFormat, TimestampPrecision, TimestampStyle};
+/// 
+/// // Write timestamps with millisecond precision in a fixed zone
+/// let style: TimestampStyle = TimestampStyle::fixed(FixedOffset::east_opt(2 * 3600).unwrap()).with_format(TimestampFormat::Rfc3339(TimestampPrecision::Millis));
+/// assert!(style.now(humanlog::DebugMode::Debug).to_string().ends_with("+02:00"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TimestampStyle {
+    /// The timezone to write timestamps in.
+    pub zone   : TimestampZone,
+    /// The format to write timestamps in.
+    pub format : TimestampFormat,
+}
+impl TimestampStyle {
+    /// Constructor for the TimestampStyle that writes timestamps in local time, using the format matching the `DebugMode`.
+    /// 
+    /// This is the default.
+    /// 
+    /// # Returns
+    /// A new TimestampStyle that writes local timestamps.
+    #[inline]
+    pub const fn local() -> Self { Self { zone: TimestampZone::Local, format: TimestampFormat::Auto } }
+
+    /// Constructor for the TimestampStyle that writes timestamps in UTC, using the format matching the `DebugMode`.
+    /// 
+    /// # Returns
+    /// A new TimestampStyle that writes UTC timestamps.
+    #[inline]
+    pub const fn utc() -> Self { Self { zone: TimestampZone::Utc, format: TimestampFormat::Auto } }
+
+    /// Constructor for the TimestampStyle that writes timestamps with a fixed offset from UTC, using the format matching the `DebugMode`.
+    /// 
+    /// # Arguments
+    /// - `offset`: The offset to write timestamps with.
+    /// 
+    /// # Returns
+    /// A new TimestampStyle that writes timestamps in the given offset.
+    #[inline]
+    pub const fn fixed(offset: FixedOffset) -> Self { Self { zone: TimestampZone::Fixed(offset), format: TimestampFormat::Auto } }
+
+    /// Constructor for the TimestampStyle that does not write timestamps at all.
+    /// 
+    /// # Returns
+    /// A new TimestampStyle that disables timestamps.
+    #[inline]
+    pub const fn none() -> Self { Self { zone: TimestampZone::Local, format: TimestampFormat::None } }
+
+    /// Changes the format of this style.
+    /// 
+    /// # Arguments
+    /// - `format`: The new `TimestampFormat` to write timestamps in.
+    /// 
+    /// # Returns
+    /// The same TimestampStyle as `self`, but with the new format set.
+    #[inline]
+    pub fn with_format(mut self, format: TimestampFormat) -> Self { self.format = format; self }
+
+
+
+    /// Returns whether this style writes anything at all.
+    #[inline]
+    pub fn is_enabled(&self) -> bool { self.format != TimestampFormat::None }
+
+    /// Returns a formatter that writes the current time in this style.
+    /// 
+    /// # Arguments
+    /// - `mode`: The `DebugMode` that decides the format if it is `TimestampFormat::Auto`.
+    /// 
+    /// # Returns
+    /// An object implementing [`Display`] that writes the current time. Writes nothing if timestamps are disabled.
+    #[inline]
+    pub fn now(&self, mode: DebugMode) -> impl '_ + Display { self.at(Utc::now(), mode) }
+
+    /// Returns a formatter that writes the given time in this style.
+    /// 
+    /// Note that `TimestampFormat::Uptime` always writes the time since the logger was created, regardless of the given time.
+    /// 
+    /// # Arguments
+    /// - `time`: The moment to write, e.g., the time at which a record was logged.
+    /// - `mode`: The `DebugMode` that decides the format if it is `TimestampFormat::Auto`.
+    /// 
+    /// # Returns
+    /// An object implementing [`Display`] that writes the given time. Writes nothing if timestamps are disabled.
+    #[inline]
+    pub fn at(&self, time: DateTime<Utc>, mode: DebugMode) -> impl '_ + Display { DisplayTimestamp { style: self, time, mode } }
+}
+
+impl FromStr for TimestampStyle {
+    type Err = TimestampStyleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "local"       => Ok(Self::local()),
+            "utc"         => Ok(Self::utc()),
+            "none"        => Ok(Self::none()),
+            "unix"        => Ok(Self::utc().with_format(TimestampFormat::UnixSeconds)),
+            "unix-millis" => Ok(Self::utc().with_format(TimestampFormat::UnixMillis)),
+            "uptime"      => Ok(Self::local().with_format(TimestampFormat::Uptime(TimestampPrecision::Millis))),
+            raw           => raw.parse::<FixedOffset>().map(Self::fixed).map_err(|_| TimestampStyleParseError { raw: s.into() }),
+        }
+    }
+}
+
+
+
+/// Writes a time in the given [`TimestampStyle`].
+struct DisplayTimestamp<'s> {
+    /// The style to write the time in.
+    style : &'s TimestampStyle,
+    /// The time to write.
+    time  : DateTime<Utc>,
+    /// The mode that decides the format if the style leaves it up to the mode.
+    mode  : DebugMode,
+}
+impl<'s> Display for DisplayTimestamp<'s> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        // Only UTC times may be written with a `Z`
+        let use_z: bool = self.style.zone == TimestampZone::Utc;
+        match (&self.style.format, self.mode) {
+            (TimestampFormat::None, _) | (TimestampFormat::Auto, DebugMode::HumanFriendly) => Ok(()),
+
+            (TimestampFormat::Auto, DebugMode::Debug) => DisplayRfc3339 { time: self.style.zone.at(self.time), precision: TimestampPrecision::Seconds, use_z }.fmt(f),
+            (TimestampFormat::Auto, DebugMode::Full)  => DisplayRfc3339 { time: self.style.zone.at(self.time), precision: TimestampPrecision::Nanos, use_z }.fmt(f),
+            (TimestampFormat::Rfc3339(precision), _)  => DisplayRfc3339 { time: self.style.zone.at(self.time), precision: *precision, use_z }.fmt(f),
+            (TimestampFormat::Custom(format), _)      => {
+                // Check the format first, as chrono would fail the whole write otherwise
+                if StrftimeItems::new(format).any(|item| item == Item::Error) { return f.write_str("<invalid timestamp format>"); }
+                self.style.zone.at(self.time).format(format).write_to(f)
+            },
+            (TimestampFormat::UnixSeconds, _) => write!(f, "{}", self.time.timestamp()),
+            (TimestampFormat::UnixMillis, _)  => write!(f, "{}", self.time.timestamp_millis()),
+            (TimestampFormat::Uptime(precision), _) => {
+                let uptime: Duration = START.get_or_init(Instant::now).elapsed();
+                match precision.digits() {
+                    0      => write!(f, "{}", uptime.as_secs()),
+                    digits => write!(f, "{}.{:0digits$}", uptime.as_secs(), uptime.subsec_nanos() / 10u32.pow(9 - digits as u32)),
+                }
+            },
+        }
+    }
+}
+
+/// Writes a time in RFC 3339, like [`DateTime::to_rfc3339_opts()`] does, but straight into the formatter instead of into a new [`String`].
+pub(crate) struct DisplayRfc3339 {
+    /// The time to write, in the zone to write it in.
+    pub(crate) time      : DateTime<FixedOffset>,
+    /// The number of subsecond digits to write.
+    pub(crate) precision : TimestampPrecision,
+    /// Whether to write a `Z` instead of a zero offset.
+    pub(crate) use_z     : bool,
+}
+impl Display for DisplayRfc3339 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        let fraction: Option<Item> = match self.precision {
+            TimestampPrecision::Seconds => None,
+            TimestampPrecision::Millis  => Some(Item::Fixed(Fixed::Nanosecond3)),
+            TimestampPrecision::Micros  => Some(Item::Fixed(Fixed::Nanosecond6)),
+            TimestampPrecision::Nanos   => Some(Item::Fixed(Fixed::Nanosecond9)),
+        };
+        // Write the offset ourselves, as chrono only writes it after copying the offset into a string
+        DisplayItems { time: self.time.naive_local(), items: &RFC3339_ITEMS }.fmt(f)?;
+        if let Some(fraction) = fraction { DisplayItems { time: self.time.naive_local(), items: &[ fraction ] }.fmt(f)?; }
+        let offset: i32 = self.time.offset().fix().local_minus_utc();
+        if offset == 0 && self.use_z { return f.write_str("Z"); }
+        let minutes: i32 = (offset.abs() + 30) / 60;
+        write!(f, "{}{:02}:{:02}", if offset < 0 { '-' } else { '+' }, minutes / 60, minutes % 60)
+    }
+}
+
+/// Writes a time according to the given chrono [`Item`]s, straight into the formatter instead of into a new [`String`].
+pub(crate) struct DisplayItems<'i> {
+    /// The time to write.
+    pub(crate) time  : NaiveDateTime,
+    /// The items that decide how to write it.
+    pub(crate) items : &'i [Item<'i>],
+}
+impl<'i> Display for DisplayItems<'i> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { self.time.format_with_items(self.items.iter()).write_to(f) }
+}
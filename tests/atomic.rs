@@ -0,0 +1,148 @@
+//  ATOMIC.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:52:39
+//  Last edited:
+//    16 Oct 2026, 15:52:39
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that records written by several (forked) processes to the same
+//!   pipe or appended file are never interleaved, as long as they fit in
+//!   `PIPE_BUF`.
+// 
+
+#![cfg(unix)]
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::Read as _;
+use std::os::fd::FromRawFd as _;
+use std::path::PathBuf;
+
+use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter};
+use log::{Level, Log as _, Record};
+
+
+/***** CONSTANTS *****/
+/// The number of processes that write at the same time.
+const CHILDREN: usize = 8;
+/// The number of records every process writes.
+const RECORDS: usize = 250;
+/// The length of the message of every record, which leaves plenty of room for the prefix within the smallest `PIPE_BUF` allowed by POSIX.
+const MESSAGE_LEN: usize = 400;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Runs the given closure in a number of forked child processes, and waits until they're all done.
+/// 
+/// # Arguments
+/// - `child`: The closure to run in every child, which gets the index of the child.
+/// - `parent`: The closure to run in the parent while the children are running.
+/// 
+/// # Panics
+/// This function panics if forking failed, or if any of the children panicked.
+fn fork_children(child: impl Fn(usize), parent: impl FnOnce()) {
+    let mut pids: Vec<libc::pid_t> = Vec::with_capacity(CHILDREN);
+    for i in 0..CHILDREN {
+        // SAFETY: The child only runs the closure and then exits without returning to the test harness
+        match unsafe { libc::fork() } {
+            -1 => panic!("Failed to fork: {}", std::io::Error::last_os_error()),
+            0 => {
+                let code: i32 = if std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| child(i))).is_ok() { 0 } else { 1 };
+                // SAFETY: Exiting immediately is exactly what we want, as the child must not run the parent's cleanup
+                unsafe { libc::_exit(code) };
+            },
+            pid => pids.push(pid),
+        }
+    }
+    parent();
+
+    // Wait for all children to finish
+    for pid in pids {
+        let mut status: i32 = 0;
+        // SAFETY: We pass a valid pointer to write the status to
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid, "Failed to wait for child {pid}");
+        assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0, "Child {pid} failed (status {status})");
+    }
+}
+
+/// Writes all records of a child to the given file.
+/// 
+/// # Arguments
+/// - `file`: The `File` to write to.
+/// - `child`: The index of the child, which is written in every record.
+fn write_records(file: File, child: usize) {
+    let logger: HumanLogger = HumanLogger::new([ LogWriter::new(file, ColourChoice::No, vec![ Level::Info ], "shared") ], DebugMode::Debug);
+    let fill: String = char::from(b'a' + child as u8).to_string().repeat(MESSAGE_LEN);
+    for n in 0..RECORDS {
+        logger.log(&Record::builder().level(Level::Info).target("atomic").args(format_args!("{child} {n:03} {fill}")).build());
+    }
+}
+
+/// Checks that the given output consists of exactly the records of all children, each on their own, complete line.
+/// 
+/// # Arguments
+/// - `output`: The output written by all children.
+/// 
+/// # Panics
+/// This function panics if any record is missing, duplicated or interleaved with another.
+fn check_records(output: &str) {
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    for line in output.lines() {
+        let message: &str = line.split_once("] ").unwrap_or_else(|| panic!("Line has no prefix: {line:?}")).1;
+        let mut parts = message.split(' ');
+        let child: usize = parts.next().and_then(|child| child.parse().ok()).unwrap_or_else(|| panic!("Line has no child: {line:?}"));
+        let n: usize = parts.next().and_then(|n| n.parse().ok()).unwrap_or_else(|| panic!("Line has no record number: {line:?}"));
+        let fill: &str = parts.next().unwrap_or_else(|| panic!("Line has no fill: {line:?}"));
+        assert!(parts.next().is_none(), "Line was interleaved: {line:?}");
+        assert!(fill.len() == MESSAGE_LEN && fill.bytes().all(|b| b == b'a' + child as u8), "Line was interleaved: {line:?}");
+        assert!(seen.insert((child, n)), "Line was written twice: {line:?}");
+    }
+    assert_eq!(seen.len(), CHILDREN * RECORDS);
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn pipe_records_are_atomic() {
+    let mut fds: [i32; 2] = [0; 2];
+    // SAFETY: We pass a valid array of two file descriptors
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0, "Failed to create pipe: {}", std::io::Error::last_os_error());
+    let (read, write): (i32, i32) = (fds[0], fds[1]);
+
+    let mut output: String = String::new();
+    fork_children(
+        |child| {
+            // SAFETY: The pipe is ours, and we fully own the forked copies of its ends
+            unsafe { libc::close(read) };
+            write_records(unsafe { File::from_raw_fd(write) }, child);
+        },
+        || {
+            // Close our end for writing first, so reading stops once all children are done
+            // SAFETY: The pipe is ours, and the children have their own copies of its ends
+            unsafe { libc::close(write) };
+            unsafe { File::from_raw_fd(read) }.read_to_string(&mut output).unwrap();
+        },
+    );
+    check_records(&output);
+}
+
+#[test]
+fn appended_file_records_are_atomic() {
+    let path: PathBuf = std::env::temp_dir().join(format!("humanlog-atomic-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    fork_children(|child| write_records(OpenOptions::new().create(true).append(true).open(&path).unwrap(), child), || {});
+    let output: String = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    check_records(&output);
+}
@@ -0,0 +1,96 @@
+//  HANDLE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:17:29
+//  Last edited:
+//    16 Oct 2026, 15:17:29
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that a [`LoggerHandle`] changes what the installed logger writes,
+//!   and that `log`'s maximum level follows every change.
+// 
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use humanlog::{ColourChoice, DebugMode, HumanLogger, LogWriter, LoggerHandle, TimestampStyle};
+use log::{debug, error, info, trace, warn, Level, LevelFilter};
+
+
+/***** HELPERS *****/
+/// A writer that keeps everything written to it in memory.
+#[derive(Clone, Default)]
+struct Memory(Arc<Mutex<Vec<u8>>>);
+impl Memory {
+    /// Returns everything that was written to this writer.
+    fn contents(&self) -> String { String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned() }
+}
+impl Write for Memory {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().write(buf) }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+
+
+
+
+/***** TESTS *****/
+// There can only be one installed logger per process, so everything is tested in one go
+#[test]
+fn handle_reconfigures_installed_logger() {
+    let (stderr, stdout): (Memory, Memory) = (Memory::default(), Memory::default());
+    let handle: LoggerHandle = HumanLogger::new(vec![
+        LogWriter::new(stderr.clone(), ColourChoice::No, [ Level::Error, Level::Warn ], "stderr"),
+        LogWriter::new(stdout.clone(), ColourChoice::No, [ Level::Info, Level::Debug, Level::Trace ], "stdout"),
+    ], DebugMode::HumanFriendly).init_with_handle().unwrap();
+    handle.set_timestamp(TimestampStyle::none());
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+    info!("Hidden");
+    warn!("Shown");
+    assert_eq!(stderr.contents(), "WARNING: Shown\n");
+    assert_eq!(stdout.contents(), "");
+
+    // The mode decides both which levels are written and how
+    handle.set_mode(DebugMode::Debug);
+    assert_eq!(handle.mode(), DebugMode::Debug);
+    assert_eq!(log::max_level(), LevelFilter::Debug);
+    debug!("Now shown");
+    trace!("Still hidden");
+    assert_eq!(stdout.contents(), "[DEBUG handle] Now shown\n");
+
+    // Filters limit what writers write, which may lower the maximum level below the mode's
+    assert!(handle.set_filter("stdout", [ Level::Info ]));
+    assert!(!handle.set_filter("nonexistent", [ Level::Info ]));
+    assert_eq!(log::max_level(), LevelFilter::Info);
+    debug!("Hidden again");
+    info!("Info");
+    assert_eq!(stdout.contents(), "[DEBUG handle] Now shown\n[INFO handle] Info\n");
+
+    // Writers can be added...
+    let extra: Memory = Memory::default();
+    handle.add_writer(LogWriter::new(extra.clone(), ColourChoice::No, [ Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace ], "extra"));
+    assert_eq!(log::max_level(), LevelFilter::Debug);
+    handle.set_mode(DebugMode::Full);
+    assert_eq!(log::max_level(), LevelFilter::Trace);
+    trace!("Traced");
+    assert!(extra.contents().ends_with(" handle] Traced\n"), "Unexpected contents: {:?}", extra.contents());
+
+    // ...and removed again
+    assert!(handle.remove_writer("extra"));
+    assert!(!handle.remove_writer("extra"));
+    assert_eq!(log::max_level(), LevelFilter::Info);
+    error!("Not for extra");
+    assert_eq!(extra.contents().lines().count(), 1);
+
+    // Colours can be changed per writer
+    handle.set_mode(DebugMode::HumanFriendly);
+    assert!(handle.set_colour("stderr", ColourChoice::Yes));
+    assert!(!handle.set_colour("nonexistent", ColourChoice::Yes));
+    error!("In colour");
+    assert!(stderr.contents().lines().last().is_some_and(|line| line.starts_with('\x1b') && line.ends_with("In colour")), "Unexpected contents: {:?}", stderr.contents());
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+}
@@ -0,0 +1,103 @@
+//  JOURNALD.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:23:58
+//  Last edited:
+//    16 Oct 2026, 15:23:58
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that a [`JournaldWriter`](humanlog::JournaldWriter) sends
+//!   records that are too large for a datagram through a sealed memory
+//!   file instead.
+// 
+
+#![cfg(target_os = "linux")]
+
+use std::fs::File;
+use std::io::{Read as _, Seek as _, SeekFrom};
+use std::mem;
+use std::os::unix::io::{AsRawFd as _, FromRawFd as _, RawFd};
+use std::os::unix::net::UnixDatagram;
+use std::path::PathBuf;
+
+use humanlog::{DebugMode, HumanLogger, JournaldFormatter, LogWriter};
+use log::{Level, Log as _, Record};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the size of the send buffer of a new Unix datagram socket, which is the largest datagram it can send.
+fn send_buffer() -> usize {
+    let socket: UnixDatagram = UnixDatagram::unbound().unwrap();
+    let mut size: libc::c_int = 0;
+    let mut len: libc::socklen_t = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    // SAFETY: The value and its length match, and the socket is valid for the duration of the call
+    let res: libc::c_int = unsafe { libc::getsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF, &mut size as *mut libc::c_int as *mut libc::c_void, &mut len) };
+    assert_eq!(res, 0, "Failed to get SO_SNDBUF: {}", std::io::Error::last_os_error());
+    size as usize
+}
+
+/// Receives a message that carries a single file descriptor, like journald does.
+/// 
+/// # Returns
+/// The length of the (otherwise ignored) payload, and the received file.
+fn recv_fd(socket: &UnixDatagram) -> (usize, File) {
+    let mut payload: [u8; 16] = [0; 16];
+    let mut iov: libc::iovec = libc::iovec { iov_base: payload.as_mut_ptr() as *mut libc::c_void, iov_len: payload.len() };
+    // SAFETY: This only computes a size
+    let space: usize = unsafe { libc::CMSG_SPACE(mem::size_of::<RawFd>() as u32) } as usize;
+    let mut control: Vec<u64> = vec![0; space.div_ceil(mem::size_of::<u64>())];
+
+    // SAFETY: The message is zero-initialized (which is valid for all its fields), and the buffers it points to outlive the call
+    let (n, fd): (isize, RawFd) = unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = space as _;
+        let n: isize = libc::recvmsg(socket.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC);
+        assert!(n >= 0, "Failed to receive message: {}", std::io::Error::last_os_error());
+
+        let cmsg: *mut libc::cmsghdr = libc::CMSG_FIRSTHDR(&msg);
+        assert!(!cmsg.is_null(), "Message has no control message");
+        assert_eq!(((*cmsg).cmsg_level, (*cmsg).cmsg_type), (libc::SOL_SOCKET, libc::SCM_RIGHTS));
+        (n, std::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const RawFd))
+    };
+    // SAFETY: The kernel just gave us this descriptor, so nobody else owns it
+    (n as usize, unsafe { File::from_raw_fd(fd) })
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn large_records_are_sent_as_memfd() {
+    // Pretend to be journald
+    let path: PathBuf = std::env::temp_dir().join(format!("humanlog-journald-memfd-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let journald: UnixDatagram = UnixDatagram::bind(&path).unwrap();
+    let writer: LogWriter = LogWriter::journald(&path, JournaldFormatter::new().with_identifier("example"), [ Level::Warn ], "journald").unwrap();
+    let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly);
+
+    // Log something that doesn't fit in a datagram
+    let message: String = "A".repeat(2 * send_buffer());
+    logger.log(&Record::builder().level(Level::Warn).target("kenobi").args(format_args!("{message}")).build());
+
+    // It's received as a file descriptor without a payload, and the file has the whole record
+    let (n, mut file): (usize, File) = recv_fd(&journald);
+    assert_eq!(n, 0);
+    let mut contents: String = String::new();
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, format!("PRIORITY=4\nMESSAGE={message}\nSYSLOG_IDENTIFIER=example\nTARGET=kenobi\n"));
+
+    // The file is sealed, so journald can trust it not to change
+    // SAFETY: The descriptor is valid for as long as `file` lives
+    let seals: libc::c_int = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GET_SEALS) };
+    assert_eq!(seals, libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE | libc::F_SEAL_SEAL);
+    std::fs::remove_file(&path).unwrap();
+}
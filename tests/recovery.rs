@@ -0,0 +1,224 @@
+//  RECOVERY.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:14:40
+//  Last edited:
+//    16 Oct 2026, 15:14:40
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that every [`ErrorPolicy`] puts a failing
+//!   [`LogWriter`](humanlog::LogWriter) in the right state, and that the
+//!   logger stops enabling levels that only dead writers wrote.
+// 
+
+use std::io::{self, ErrorKind, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use humanlog::{ColourChoice, DebugMode, ErrorAction, ErrorPolicy, HumanLogger, LogWriter, WriterStatus};
+use log::{Level, Log as _, Metadata, Record};
+
+
+/***** HELPERS *****/
+/// A writer that fails with a configurable error, and counts how often it is written to.
+#[derive(Clone, Default)]
+struct Flaky {
+    /// The buffer that successful writes end up in.
+    memory   : Arc<Mutex<Vec<u8>>>,
+    /// The kind of error to fail with, or [`None`] to succeed.
+    failing  : Arc<Mutex<Option<ErrorKind>>>,
+    /// The number of times this writer was written to, failed or not.
+    attempts : Arc<Mutex<usize>>,
+}
+impl Flaky {
+    /// Makes the writer fail with the given kind of error, or succeed again if [`None`].
+    fn fail(&self, kind: Option<ErrorKind>) { *self.failing.lock().unwrap() = kind; }
+
+    /// Returns the number of times this writer was written to.
+    fn attempts(&self) -> usize { *self.attempts.lock().unwrap() }
+
+    /// Returns everything that was successfully written to this writer.
+    fn contents(&self) -> String { String::from_utf8_lossy(&self.memory.lock().unwrap()).into_owned() }
+}
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        *self.attempts.lock().unwrap() += 1;
+        match *self.failing.lock().unwrap() {
+            Some(kind) => Err(io::Error::new(kind, "Flaky writer failed")),
+            None       => self.memory.lock().unwrap().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Logs a warning with the given message to the given logger.
+fn warn(logger: &HumanLogger, message: &str) { logger.log(&Record::builder().level(Level::Warn).args(format_args!("{message}")).build()); }
+
+/// Returns whether the given logger would write a record of the given level.
+fn enabled(logger: &HumanLogger, level: Level) -> bool { logger.enabled(&Metadata::builder().level(level).build()) }
+
+/// Returns the only writer that is not written to, or panics if there isn't exactly one.
+fn only_status(statuses: Vec<WriterStatus>) -> WriterStatus {
+    assert_eq!(statuses.len(), 1, "Expected exactly one disabled writer, got {statuses:?}");
+    statuses.into_iter().next().unwrap()
+}
+
+/// Waits until the given time has passed.
+fn sleep_until(time: Instant) { thread::sleep(time.saturating_duration_since(Instant::now())); }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn disable_stops_writing_for_good() {
+    // The failing writer is the only one that writes warnings, and is installed so the global state is tested too
+    let flaky: Flaky = Flaky::default();
+    let errors: Flaky = Flaky::default();
+    HumanLogger::new(vec![
+        LogWriter::new(flaky.clone(), ColourChoice::No, [ Level::Warn ], "flaky"),
+        LogWriter::new(errors.clone(), ColourChoice::No, [ Level::Error ], "errors"),
+    ], DebugMode::HumanFriendly).init().unwrap();
+    assert!(log::log_enabled!(Level::Warn));
+
+    flaky.fail(Some(ErrorKind::Other));
+    log::warn!("Lost");
+    let status: WriterStatus = only_status(humanlog::disabled_writers());
+    assert_eq!(status.label, "flaky");
+    assert_eq!(status.error, "Flaky writer failed");
+    assert!(status.retry_at.is_none());
+
+    // It's never written to again, even if it would work now, and warnings are no longer enabled while errors still are
+    flaky.fail(None);
+    log::warn!("Also lost");
+    assert_eq!(flaky.attempts(), 1);
+    assert_eq!(flaky.contents(), "");
+    assert!(!log::log_enabled!(Level::Warn));
+    assert!(log::log_enabled!(Level::Error));
+    log::error!("Still written");
+    assert_eq!(errors.contents(), "ERROR: Still written\n");
+}
+
+#[test]
+fn retry_backs_off_and_recovers() {
+    let flaky: Flaky = Flaky::default();
+    let writer: LogWriter = LogWriter::new(flaky.clone(), ColourChoice::No, [ Level::Warn ], "flaky").with_error_policy(ErrorPolicy::retry(Duration::from_millis(50), Duration::from_millis(80)));
+    let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly);
+
+    // The first failure suspends the writer for the initial delay, during which it's skipped but still enabled
+    flaky.fail(Some(ErrorKind::Other));
+    let start: Instant = Instant::now();
+    warn(&logger, "Lost");
+    let first: Instant = only_status(logger.disabled_writers()).retry_at.expect("Suspended writer has no retry time");
+    assert!(first >= start + Duration::from_millis(50));
+    warn(&logger, "Skipped");
+    assert_eq!(flaky.attempts(), 1);
+    assert!(enabled(&logger, Level::Warn));
+
+    // Failing again doubles the delay, but never beyond the maximum
+    sleep_until(first);
+    let before: Instant = Instant::now();
+    warn(&logger, "Lost again");
+    let after: Instant = Instant::now();
+    assert_eq!(flaky.attempts(), 2);
+    let second: Instant = only_status(logger.disabled_writers()).retry_at.expect("Suspended writer has no retry time");
+    assert!(second >= before + Duration::from_millis(80) && second <= after + Duration::from_millis(80), "Expected a delay of 80ms, got {:?}", second - before);
+
+    // Once it works again, the writer is enabled as if nothing happened
+    flaky.fail(None);
+    sleep_until(second);
+    warn(&logger, "Written");
+    warn(&logger, "Written too");
+    assert!(logger.disabled_writers().is_empty());
+    assert_eq!(flaky.contents(), "WARNING: Written\nWARNING: Written too\n");
+}
+
+#[test]
+fn retry_without_end_disables() {
+    let flaky: Flaky = Flaky::default();
+    let writer: LogWriter = LogWriter::new(flaky.clone(), ColourChoice::No, [ Level::Warn ], "flaky").with_error_policy(ErrorPolicy::retry(Duration::MAX, Duration::MAX));
+    let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly);
+
+    // Waiting longer than time can represent is the same as never trying again (which doesn't panic)
+    flaky.fail(Some(ErrorKind::Other));
+    warn(&logger, "Lost");
+    let status: WriterStatus = only_status(logger.disabled_writers());
+    assert_eq!(status.error, "Flaky writer failed");
+    assert!(status.retry_at.is_none());
+    flaky.fail(None);
+    warn(&logger, "Also lost");
+    assert_eq!(flaky.attempts(), 1);
+    assert!(!enabled(&logger, Level::Warn));
+}
+
+#[test]
+fn reopen_replaces_the_writer() {
+    // The original writer always fails, but the one that replaces it works
+    let broken: Flaky = Flaky::default();
+    broken.fail(Some(ErrorKind::BrokenPipe));
+    let reopened: Flaky = Flaky::default();
+    let opens: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
+    let policy: ErrorPolicy = {
+        let (reopened, opens): (Flaky, Arc<Mutex<usize>>) = (reopened.clone(), opens.clone());
+        ErrorPolicy::reopen(Duration::from_millis(20), Duration::from_secs(1), move || {
+            *opens.lock().unwrap() += 1;
+            Ok(reopened.clone())
+        })
+    };
+    let logger: HumanLogger = HumanLogger::new(vec![ LogWriter::new(broken.clone(), ColourChoice::No, [ Level::Warn ], "broken").with_error_policy(policy) ], DebugMode::HumanFriendly);
+
+    warn(&logger, "Lost");
+    let status: WriterStatus = only_status(logger.disabled_writers());
+    assert_eq!(status.error, "Flaky writer failed");
+    assert_eq!(*opens.lock().unwrap(), 0);
+
+    // The writer is only reopened once it's time to try again
+    sleep_until(status.retry_at.expect("Suspended writer has no retry time"));
+    warn(&logger, "Written");
+    warn(&logger, "Written too");
+    assert_eq!(*opens.lock().unwrap(), 1);
+    assert_eq!(broken.attempts(), 1);
+    assert!(logger.disabled_writers().is_empty());
+    assert_eq!(reopened.contents(), "WARNING: Written\nWARNING: Written too\n");
+}
+
+#[test]
+fn callback_decides_what_happens() {
+    let flaky: Flaky = Flaky::default();
+    let calls: Arc<Mutex<Vec<(String, ErrorKind)>>> = Arc::new(Mutex::new(vec![]));
+    let policy: ErrorPolicy = {
+        let calls: Arc<Mutex<Vec<(String, ErrorKind)>>> = calls.clone();
+        ErrorPolicy::callback(move |label, err| {
+            calls.lock().unwrap().push((label.into(), err.kind()));
+            if err.kind() == ErrorKind::WouldBlock { ErrorAction::Keep } else { ErrorAction::Disable }
+        })
+    };
+    let logger: HumanLogger = HumanLogger::new(vec![ LogWriter::new(flaky.clone(), ColourChoice::No, [ Level::Warn ], "flaky").with_error_policy(policy) ], DebugMode::HumanFriendly);
+
+    // Errors the callback keeps the writer for don't stop the next record
+    flaky.fail(Some(ErrorKind::WouldBlock));
+    warn(&logger, "Lost");
+    assert!(logger.disabled_writers().is_empty());
+    flaky.fail(None);
+    warn(&logger, "Written");
+    assert_eq!(flaky.contents(), "WARNING: Written\n");
+
+    // Other errors disable it, after which the level is no longer enabled
+    flaky.fail(Some(ErrorKind::Other));
+    warn(&logger, "Lost again");
+    assert_eq!(only_status(logger.disabled_writers()).label, "flaky");
+    assert!(!enabled(&logger, Level::Warn));
+    assert_eq!(*calls.lock().unwrap(), vec![ ("flaky".to_string(), ErrorKind::WouldBlock), ("flaky".to_string(), ErrorKind::Other) ]);
+    assert_eq!(flaky.attempts(), 3);
+}
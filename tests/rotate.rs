@@ -0,0 +1,127 @@
+//  ROTATE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:09:00
+//  Last edited:
+//    16 Oct 2026, 15:09:00
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that a [`RotatingFile`] rotates when it should, names and prunes
+//!   the rotated files according to its policy, and never splits a record
+//!   across two files.
+// 
+
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use humanlog::{DebugMode, HumanLogger, LogWriter, RotatingFile, RotationNaming, RotationPolicy};
+use log::{Level, Log as _, Record};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Creates a new, empty directory to rotate files in.
+/// 
+/// # Arguments
+/// - `name`: The name of the test, which keeps the directories of tests running in parallel apart.
+/// 
+/// # Returns
+/// The path of the directory.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir: PathBuf = std::env::temp_dir().join(format!("humanlog-rotate-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Returns the names of all files in the given directory, sorted.
+fn files(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    files.sort();
+    files
+}
+
+/// Returns the contents of the given file in the given directory.
+fn read(dir: &Path, name: &str) -> String { fs::read_to_string(dir.join(name)).unwrap_or_else(|err| panic!("Failed to read '{name}': {err}")) }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn rotates_when_too_large() {
+    let dir: PathBuf = temp_dir("size");
+    let writer: LogWriter = LogWriter::rotating(dir.join("out.log"), RotationPolicy::new().with_max_size(64), [ Level::Warn ], "file").unwrap();
+    let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly);
+
+    // Every record is 25 bytes, so the fourth one is the first that no longer fits
+    for n in 0..5 {
+        logger.log(&Record::builder().level(Level::Warn).args(format_args!("Record number {n}")).build());
+    }
+    assert_eq!(files(&dir), vec![ "out.log", "out.log.1" ]);
+    assert_eq!(read(&dir, "out.log.1"), "WARNING: Record number 0\nWARNING: Record number 1\nWARNING: Record number 2\n");
+    assert_eq!(read(&dir, "out.log"), "WARNING: Record number 3\nWARNING: Record number 4\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn numbered_files_are_shifted_and_pruned() {
+    let dir: PathBuf = temp_dir("numbered");
+    let mut file: RotatingFile = RotatingFile::open(dir.join("out.log"), RotationPolicy::new().with_max_size(1).with_keep(2)).unwrap();
+
+    // Every record is rotated away by the next, and only the two most recent ones are kept
+    for n in 0..5 {
+        writeln!(file, "Record {n}").unwrap();
+    }
+    assert_eq!(files(&dir), vec![ "out.log", "out.log.1", "out.log.2" ]);
+    assert_eq!(read(&dir, "out.log"), "Record 4\n");
+    assert_eq!(read(&dir, "out.log.1"), "Record 3\n");
+    assert_eq!(read(&dir, "out.log.2"), "Record 2\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn timestamped_files_are_pruned() {
+    let dir: PathBuf = temp_dir("timestamped");
+    let mut file: RotatingFile = RotatingFile::open(dir.join("out.log"), RotationPolicy::new().with_max_size(1).with_keep(2).with_naming(RotationNaming::Timestamped)).unwrap();
+
+    // Leave some time between records, so their files are pruned by age rather than by name
+    for n in 0..5 {
+        writeln!(file, "Record {n}").unwrap();
+        thread::sleep(Duration::from_millis(20));
+    }
+    let files: Vec<String> = files(&dir);
+    assert_eq!(files.len(), 3, "Expected the current and two rotated files, got {files:?}");
+    assert_eq!(read(&dir, "out.log"), "Record 4\n");
+    let mut rotated: Vec<String> = files.iter().filter(|name| *name != "out.log").map(|name| read(&dir, name)).collect();
+    rotated.sort();
+    assert_eq!(rotated, vec![ "Record 2\n", "Record 3\n" ]);
+    assert!(files.iter().all(|name| name == "out.log" || name.strip_prefix("out.log.").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))), "Unexpected names: {files:?}");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn records_are_never_split() {
+    let dir: PathBuf = temp_dir("split");
+    let mut file: RotatingFile = RotatingFile::open(dir.join("out.log"), RotationPolicy::new().with_max_size(8).with_keep(10)).unwrap();
+
+    // Write every record in pieces, each of which would exceed the maximum size on its own
+    for n in 0..4 {
+        file.write_all(b"Record ").unwrap();
+        write!(file, "number {n}").unwrap();
+        file.write_all(b" is done\n").unwrap();
+    }
+
+    // Every record ended up whole in its own file, the oldest one being rotated the furthest
+    assert_eq!(files(&dir), vec![ "out.log", "out.log.1", "out.log.2", "out.log.3" ]);
+    for (name, n) in [ ("out.log.3", 0), ("out.log.2", 1), ("out.log.1", 2), ("out.log", 3) ] {
+        assert_eq!(read(&dir, name), format!("Record number {n} is done\n"), "File '{name}' has a split record");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
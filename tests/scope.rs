@@ -0,0 +1,76 @@
+//  SCOPE.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:39:16
+//  Last edited:
+//    16 Oct 2026, 15:39:16
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that scopes are introduced to every writer and [`Capture`] that
+//!   actually receives a record in them, no matter when it started
+//!   receiving records.
+// 
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use humanlog::capture::{Capture, MemoryWriter};
+use humanlog::{scope, ColourChoice, DebugMode, ErrorAction, ErrorPolicy, HumanLogger, LogWriter};
+use log::{info, Level, Log as _, Record};
+
+
+/***** HELPERS *****/
+/// A writer that fails for as long as it's told to.
+#[derive(Clone, Default)]
+struct Flaky {
+    /// The buffer that successful writes end up in.
+    memory  : MemoryWriter,
+    /// Whether writes currently fail.
+    failing : Arc<Mutex<bool>>,
+}
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if *self.failing.lock().unwrap() { return Err(io::Error::other("Flaky writer failed")); }
+        self.memory.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn later_captures_get_headers() {
+    let _request = scope::enter("request 4f2a");
+    info!("Not captured");
+
+    // Every capture gets the header of the scope it was started in, even if records were logged in that scope before
+    for message in [ "First", "Second" ] {
+        let capture: Capture = Capture::start().with_mode(DebugMode::HumanFriendly);
+        info!("{message}");
+        info!("{message} again");
+        assert_eq!(capture.text(), format!("request 4f2a:\n  INFO: {message}\n  INFO: {message} again\n"));
+    }
+}
+
+#[test]
+fn failed_writes_dont_introduce_scopes() {
+    // Keep the writer around no matter what fails
+    let flaky: Flaky = Flaky::default();
+    let writer: LogWriter = LogWriter::new(flaky.clone(), ColourChoice::No, [ Level::Warn ], "flaky").with_error_policy(ErrorPolicy::callback(|_, _| ErrorAction::Keep));
+    let logger: HumanLogger = HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly);
+
+    // The header is written with the first record that the writer actually gets
+    let _request = scope::enter("request 4f2a");
+    *flaky.failing.lock().unwrap() = true;
+    logger.log(&Record::builder().level(Level::Warn).args(format_args!("Lost")).build());
+    *flaky.failing.lock().unwrap() = false;
+    logger.log(&Record::builder().level(Level::Warn).args(format_args!("Written")).build());
+    assert_eq!(flaky.memory.contents(), "request 4f2a:\n  WARNING: Written\n");
+}
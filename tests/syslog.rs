@@ -0,0 +1,99 @@
+//  SYSLOG.rs
+//    by Lut99
+// 
+//  Created:
+//    16 Oct 2026, 15:22:13
+//  Last edited:
+//    16 Oct 2026, 15:22:13
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Tests that a [`SyslogWriter`](humanlog::SyslogWriter) sends records
+//!   over UDP and TCP, and that the [`SyslogFormatter`] keeps its header
+//!   fields valid.
+// 
+
+use std::io::{BufRead as _, BufReader, Read as _};
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::time::Duration;
+
+use humanlog::{DebugMode, FormatContext, Formatter as _, HumanLogger, LogWriter, SyslogFormat, SyslogFormatter, SyslogTransport};
+use log::{Level, Log as _, Record};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Creates a logger that sends warnings to the given syslog transport.
+/// 
+/// # Arguments
+/// - `transport`: The `SyslogTransport` to send them with.
+/// 
+/// # Returns
+/// A new HumanLogger with a single syslog writer.
+fn logger(transport: SyslogTransport) -> HumanLogger {
+    let writer: LogWriter = LogWriter::syslog(transport, SyslogFormatter::new().with_app_name("example").with_procid("42"), [ Level::Warn ], "syslog").unwrap();
+    HumanLogger::new(vec![ writer ], DebugMode::HumanFriendly)
+}
+
+/// Logs a warning with the given message to the given logger.
+fn warn(logger: &HumanLogger, message: &str) { logger.log(&Record::builder().level(Level::Warn).target("kenobi").args(format_args!("{message}")).build()); }
+
+/// Receives a single datagram from the given socket as a string.
+fn recv(daemon: &UdpSocket) -> String {
+    let mut buf: [u8; 1024] = [0; 1024];
+    let n: usize = daemon.recv(&mut buf).unwrap();
+    String::from_utf8(buf[..n].to_vec()).unwrap()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn udp_sends_one_datagram_per_record() {
+    // Pretend to be a daemon on IPv4 and, if the system has it, on IPv6, so the writer has to bind to the matching family
+    for local in [ "127.0.0.1:0", "[::1]:0" ] {
+        let Ok(daemon) = UdpSocket::bind(local) else { continue; };
+        daemon.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let addr: SocketAddr = daemon.local_addr().unwrap();
+        let logger: HumanLogger = logger(SyslogTransport::Udp(addr.to_string()));
+
+        warn(&logger, "Hello there!");
+        warn(&logger, "General Kenobi!");
+        let (first, second): (String, String) = (recv(&daemon), recv(&daemon));
+        assert!(first.starts_with("<12>1 ") && first.ends_with(" example 42 kenobi - Hello there!"), "Unexpected message over {addr}: {first:?}");
+        assert!(second.starts_with("<12>1 ") && second.ends_with(" example 42 kenobi - General Kenobi!"), "Unexpected message over {addr}: {second:?}");
+    }
+}
+
+#[test]
+fn tcp_frames_by_octet_counting() {
+    let daemon: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let logger: HumanLogger = logger(SyslogTransport::Tcp(daemon.local_addr().unwrap().to_string()));
+    let (stream, _): (TcpStream, SocketAddr) = daemon.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut stream: BufReader<TcpStream> = BufReader::new(stream);
+
+    // Every message is prefixed by its length in bytes and a space, without a trailing newline (and the length counts bytes, not characters)
+    warn(&logger, "Hello there!");
+    warn(&logger, "Größe matters");
+    for expected in [ " example 42 kenobi - Hello there!", " example 42 kenobi - Größe matters" ] {
+        let mut len: Vec<u8> = vec![];
+        stream.read_until(b' ', &mut len).unwrap();
+        let len: usize = std::str::from_utf8(&len).unwrap().trim_end().parse().unwrap();
+        let mut message: Vec<u8> = vec![0; len];
+        stream.read_exact(&mut message).unwrap();
+        let message: String = String::from_utf8(message).unwrap();
+        assert!(message.starts_with("<12>1 ") && message.ends_with(expected), "Unexpected message: {message:?}");
+    }
+}
+
+#[test]
+fn rfc3164_sanitises_procid() {
+    let formatter: SyslogFormatter = SyslogFormatter::new().with_format(SyslogFormat::Rfc3164).with_hostname("tatooine").with_app_name("example").with_procid("worker 1\n");
+    let mut buf: Vec<u8> = vec![];
+    formatter.format(&mut buf, &Record::builder().level(Level::Warn).args(format_args!("Hello there!")).build(), &FormatContext::new(false, DebugMode::HumanFriendly)).unwrap();
+    let message: String = String::from_utf8(buf).unwrap();
+    assert!(message.ends_with(" tatooine example[worker_1_]: Hello there!\n"), "Unexpected message: {message:?}");
+}